@@ -0,0 +1,91 @@
+//! Router benchmarks, nightly only
+//!
+//! ```bash
+//! cargo +nightly bench --bench router
+//! ```
+#![cfg_attr(actix_nightly, feature(test))]
+
+#[cfg(actix_nightly)]
+extern crate actix_web;
+#[cfg(actix_nightly)]
+extern crate test;
+
+#[cfg(actix_nightly)]
+mod benches {
+    use actix_web::dev::{Resource, ResourceHandler, Router};
+    use actix_web::server::ServerSettings;
+    use actix_web::test::TestRequest;
+    use test::Bencher;
+
+    fn patterns(size: usize) -> Vec<String> {
+        let mut patterns = Vec::new();
+        for idx in 0..size / 4 {
+            patterns.push(format!("/api/v1/resource{}", idx));
+            patterns.push(format!("/api/v1/resource{}/{{id}}", idx));
+            patterns.push(format!("/api/v1/resource{}/{{id}}/items", idx));
+            patterns.push(format!("/api/v1/resource{}/{{id}}/items/{{item:\\d+}}", idx));
+        }
+        patterns
+    }
+
+    fn router(size: usize) -> Router {
+        let routes = patterns(size)
+            .iter()
+            .map(|p| (Resource::new("", p), Some(ResourceHandler::<()>::default())))
+            .collect();
+        Router::new("", ServerSettings::default(), routes).0
+    }
+
+    fn has_route(b: &mut Bencher, size: usize) {
+        let router = router(size);
+        let path = format!("/api/v1/resource{}/10/items/20", size / 4 - 1);
+        let mut req = TestRequest::with_uri(&path).finish();
+        assert!(router.recognize(&mut req).is_some());
+
+        b.iter(|| {
+            assert!(router.has_route(&path));
+        });
+    }
+
+    /// Sequential matching of every pattern, for comparison
+    fn sequential(b: &mut Bencher, size: usize) {
+        let resources: Vec<_> = patterns(size)
+            .iter()
+            .map(|p| Resource::new("", p))
+            .collect();
+        let path = format!("/api/v1/resource{}/10/items/20", size / 4 - 1);
+        b.iter(|| {
+            assert!(resources.iter().any(|r| r.is_match(&path)));
+        });
+    }
+
+    #[bench]
+    fn has_route_40(b: &mut Bencher) {
+        has_route(b, 40)
+    }
+
+    #[bench]
+    fn has_route_400(b: &mut Bencher) {
+        has_route(b, 400)
+    }
+
+    #[bench]
+    fn has_route_4000(b: &mut Bencher) {
+        has_route(b, 4000)
+    }
+
+    #[bench]
+    fn sequential_40(b: &mut Bencher) {
+        sequential(b, 40)
+    }
+
+    #[bench]
+    fn sequential_400(b: &mut Bencher) {
+        sequential(b, 400)
+    }
+
+    #[bench]
+    fn sequential_4000(b: &mut Bencher) {
+        sequential(b, 4000)
+    }
+}
@@ -3,15 +3,25 @@ use std::collections::HashMap;
 use std::mem;
 use std::rc::Rc;
 
+use body::Binary;
+use erased_serde;
+use error::Error;
 use handler::Reply;
 use handler::{FromRequest, Handler, Responder, RouteHandler, WrapHandler};
 use header::ContentEncoding;
+use helpers::PathPolicy;
 use http::Method;
 use httprequest::HttpRequest;
+use httpresponse::HttpResponse;
+use mime::Mime;
 use middleware::Middleware;
+use negotiate::{self, ResponseFormat};
+use openapi::OpenApi;
 use pipeline::{HandlerType, Pipeline, PipelineHandler};
+use pred::{self, HostPredicate, Predicate};
 use resource::ResourceHandler;
-use router::{Resource, Router};
+use router::{find_conflicts, ConflictPolicy, Resource, RouteConflict, RouteInfo,
+             Router};
 use server::{HttpHandler, HttpHandlerTask, IntoHttpHandler, ServerSettings};
 
 #[deprecated(since = "0.5.0", note = "please use `actix_web::App` instead")]
@@ -22,6 +32,7 @@ pub struct HttpApplication<S = ()> {
     state: Rc<S>,
     prefix: String,
     prefix_len: usize,
+    hosts: Vec<HostPredicate<()>>,
     router: Router,
     inner: Rc<UnsafeCell<Inner<S>>>,
     middlewares: Rc<Vec<Box<Middleware<S>>>>,
@@ -31,6 +42,7 @@ pub(crate) struct Inner<S> {
     prefix: usize,
     default: ResourceHandler<S>,
     encoding: ContentEncoding,
+    policy: PathPolicy,
     resources: Vec<ResourceHandler<S>>,
     handlers: Vec<(String, Box<RouteHandler<S>>)>,
 }
@@ -47,6 +59,9 @@ impl<S: 'static> PipelineHandler<S> for Inner<S> {
             }
             HandlerType::Handler(idx) => self.handlers[idx].1.handle(req),
             HandlerType::Default => self.default.handle(req, None),
+            HandlerType::Redirect => {
+                Reply::response(self.policy.redirect_response(&req))
+            }
         }
     }
 }
@@ -57,33 +72,41 @@ impl<S: 'static> HttpApplication<S> {
         unsafe { &*self.inner.get() }
     }
 
+    /// Index of handler registered with `App::handler()` for request's path
+    fn handler_idx(&self, req: &HttpRequest<S>) -> Option<usize> {
+        let inner = self.as_ref();
+        let path = &req.path()[inner.prefix..];
+        inner.handlers.iter().position(|&(ref prefix, _)| {
+            path.starts_with(prefix)
+                && (path.len() == prefix.len()
+                    || path.split_at(prefix.len()).1.starts_with('/'))
+        })
+    }
+
     #[inline]
     fn get_handler(&self, req: &mut HttpRequest<S>) -> HandlerType {
+        // path policy is not applied to handler prefixes, i.e. static files
+        let mut handler = self.handler_idx(req);
+        if handler.is_none() {
+            if self.as_ref().policy.apply(req) {
+                return HandlerType::Redirect;
+            }
+            handler = self.handler_idx(req);
+        }
         if let Some(idx) = self.router.recognize(req) {
             HandlerType::Normal(idx)
-        } else {
+        } else if let Some(idx) = handler {
             let inner = self.as_ref();
-            for idx in 0..inner.handlers.len() {
-                let &(ref prefix, _) = &inner.handlers[idx];
-                let m = {
-                    let path = &req.path()[inner.prefix..];
-                    path.starts_with(prefix)
-                        && (path.len() == prefix.len()
-                            || path.split_at(prefix.len()).1.starts_with('/'))
-                };
-
-                if m {
-                    let path: &'static str = unsafe {
-                        mem::transmute(&req.path()[inner.prefix + prefix.len()..])
-                    };
-                    if path.is_empty() {
-                        req.match_info_mut().add("tail", "");
-                    } else {
-                        req.match_info_mut().add("tail", path.split_at(1).1);
-                    }
-                    return HandlerType::Handler(idx);
-                }
+            let path: &'static str = unsafe {
+                mem::transmute(&req.path()[inner.prefix + inner.handlers[idx].0.len()..])
+            };
+            if path.is_empty() {
+                req.match_info_mut().add("tail", "");
+            } else {
+                req.match_info_mut().add("tail", path.split_at(1).1);
             }
+            HandlerType::Handler(idx)
+        } else {
             HandlerType::Default
         }
     }
@@ -101,13 +124,18 @@ impl<S: 'static> HttpApplication<S> {
 }
 
 impl<S: 'static> HttpHandler for HttpApplication<S> {
-    fn handle(&mut self, req: HttpRequest) -> Result<Box<HttpHandlerTask>, HttpRequest> {
+    fn handle(
+        &mut self, mut req: HttpRequest
+    ) -> Result<Box<HttpHandlerTask>, HttpRequest> {
         let m = {
             let path = req.path();
             path.starts_with(&self.prefix)
                 && (path.len() == self.prefix_len
                     || path.split_at(self.prefix_len).1.starts_with('/'))
         };
+        let m = m
+            && (self.hosts.is_empty()
+                || self.hosts.iter().any(|host| host.check(&mut req)));
         if m {
             let mut req = req.with_state(Rc::clone(&self.state), self.router.clone());
             let tp = self.get_handler(&mut req);
@@ -127,6 +155,7 @@ impl<S: 'static> HttpHandler for HttpApplication<S> {
 struct ApplicationParts<S> {
     state: S,
     prefix: String,
+    hosts: Vec<HostPredicate<()>>,
     settings: ServerSettings,
     default: ResourceHandler<S>,
     resources: Vec<(Resource, Option<ResourceHandler<S>>)>,
@@ -134,6 +163,10 @@ struct ApplicationParts<S> {
     external: HashMap<String, Resource>,
     encoding: ContentEncoding,
     middlewares: Vec<Box<Middleware<S>>>,
+    error_handler: Option<Rc<Fn(Error, HttpRequest) -> Error>>,
+    formats: Vec<(Mime, ResponseFormat)>,
+    conflicts: ConflictPolicy,
+    policy: PathPolicy,
 }
 
 /// Structure that follows the builder pattern for building application
@@ -150,6 +183,7 @@ impl App<()> {
             parts: Some(ApplicationParts {
                 state: (),
                 prefix: "/".to_owned(),
+                hosts: Vec::new(),
                 settings: ServerSettings::default(),
                 default: ResourceHandler::default_not_found(),
                 resources: Vec::new(),
@@ -157,6 +191,10 @@ impl App<()> {
                 external: HashMap::new(),
                 encoding: ContentEncoding::Auto,
                 middlewares: Vec::new(),
+                error_handler: None,
+                formats: Vec::new(),
+                conflicts: ConflictPolicy::default(),
+                policy: PathPolicy::default(),
             }),
         }
     }
@@ -182,6 +220,7 @@ where
             parts: Some(ApplicationParts {
                 state,
                 prefix: "/".to_owned(),
+                hosts: Vec::new(),
                 settings: ServerSettings::default(),
                 default: ResourceHandler::default_not_found(),
                 resources: Vec::new(),
@@ -189,6 +228,10 @@ where
                 external: HashMap::new(),
                 middlewares: Vec::new(),
                 encoding: ContentEncoding::Auto,
+                error_handler: None,
+                formats: Vec::new(),
+                conflicts: ConflictPolicy::default(),
+                policy: PathPolicy::default(),
             }),
         }
     }
@@ -238,6 +281,57 @@ where
         self
     }
 
+    /// Set host name that is served by this application.
+    ///
+    /// This method could be called multiple times to serve several
+    /// host names. If host name is set, application handles only requests
+    /// with matching host, as reported by `ConnectionInfo::host()`.
+    /// Application without host names handles requests for any host.
+    ///
+    /// Host name could be an exact name, i.e. `www.example.com`, or
+    /// a wildcard, i.e. `*.example.com`. Wildcard with a name,
+    /// `{name}.example.com`, stores subdomain to request's `match_info()`.
+    /// Port is ignored.
+    ///
+    /// Server checks applications in registration order, so applications
+    /// with exact host names should be registered before applications
+    /// with wildcard host names, and default application should be last.
+    ///
+    /// ```rust
+    /// # use std::thread;
+    /// # extern crate actix_web;
+    /// use actix_web::{server, App, HttpRequest, HttpResponse};
+    ///
+    /// fn main() {
+    /// # thread::spawn(|| {
+    ///     server::new(|| vec![
+    ///         App::new()
+    ///             .host("www.example.com")
+    ///             .host("example.com")
+    ///             .resource("/", |r| r.f(|_| HttpResponse::Ok()))
+    ///             .boxed(),
+    ///         App::new()
+    ///             .host("{user}.example.com")
+    ///             .resource("/", |r| r.f(|req: HttpRequest| {
+    ///                 format!("Welcome {}!", &req.match_info()["user"])
+    ///             }))
+    ///             .boxed(),
+    ///         App::new()
+    ///             .default_resource(|r| r.f(|_| HttpResponse::NotFound()))
+    ///             .boxed(),
+    ///     ]).bind("127.0.0.1:8080").unwrap()
+    ///         .run()
+    /// # });
+    /// }
+    /// ```
+    pub fn host<H: AsRef<str>>(mut self, host: H) -> App<S> {
+        {
+            let parts = self.parts.as_mut().expect("Use after finish");
+            parts.hosts.push(pred::Host(host));
+        }
+        self
+    }
+
     /// Configure route for a specific path.
     ///
     /// This is a simplified version of the `App::resource()` method.
@@ -304,6 +398,14 @@ where
     ///
     /// You can also specify a custom regex in the form `{identifier:regex}`:
     ///
+    /// Tail parameter `{identifier}*` matches rest of the path including
+    /// slashes, i.e. `/static/{tail}*` matches `/static/css/main.css`.
+    /// It has to be at the end of the pattern.
+    ///
+    /// Trailing segments could be optional, `/{identifier}?`. For example,
+    /// `/archive/{year}?/{month}?` matches `/archive`, `/archive/2018` and
+    /// `/archive/2018/04`. Missing parameters are not stored in `Params`.
+    ///
     /// For instance, to route `GET`-requests on any route matching
     /// `/users/{userid}/{friend}` and store `userid` and `friend` in
     /// the exposed `Params` object:
@@ -369,6 +471,110 @@ where
         self
     }
 
+    /// Set path normalization policy.
+    ///
+    /// Policy is applied to request's path before route recognition,
+    /// path is either rewritten or request is redirected to normalized path.
+    /// Resource patterns are normalized as well, so `HttpRequest::url_for()`
+    /// generates normalized urls. By default path is not changed.
+    ///
+    /// ```rust
+    /// # extern crate actix_web;
+    /// use actix_web::http::{PathPolicy, StatusCode, TrailingSlash};
+    /// use actix_web::{App, HttpResponse};
+    ///
+    /// fn main() {
+    ///     let app = App::new()
+    ///         .path_policy(
+    ///             PathPolicy::new()
+    ///                 .merge_slashes(true)
+    ///                 .trailing_slash(TrailingSlash::Append)
+    ///                 .redirect(StatusCode::MOVED_PERMANENTLY),
+    ///         )
+    ///         .resource("/test", |r| r.f(|_| HttpResponse::Ok()))
+    ///         .finish();
+    /// }
+    /// ```
+    pub fn path_policy(mut self, policy: PathPolicy) -> App<S> {
+        {
+            let parts = self.parts.as_mut().expect("Use after finish");
+            parts.policy = policy;
+        }
+        self
+    }
+
+    /// Set default error handler for request extractors.
+    ///
+    /// Handler is called with extraction error of any built-in extractor,
+    /// unless extractor's config has its own error handler. Returned error
+    /// is used as a response.
+    ///
+    /// ```rust
+    /// # extern crate actix_web;
+    /// use actix_web::{error, http, App, HttpResponse, Path};
+    ///
+    /// fn main() {
+    ///     let app = App::new()
+    ///         .extractor_error_handler(|err, _req| {
+    ///             let body = format!("{{\"error\": \"{}\"}}", err);
+    ///             error::InternalError::from_response(
+    ///                 err,
+    ///                 HttpResponse::BadRequest()
+    ///                     .content_type("application/json")
+    ///                     .body(body),
+    ///             ).into()
+    ///         })
+    ///         .resource("/{id}", |r| {
+    ///             r.method(http::Method::GET).with(|id: Path<u32>| format!("{}", *id))
+    ///         })
+    ///         .finish();
+    /// }
+    /// ```
+    pub fn extractor_error_handler<F>(mut self, f: F) -> App<S>
+    where
+        F: Fn(Error, HttpRequest) -> Error + 'static,
+    {
+        {
+            let parts = self.parts.as_mut().expect("Use after finish");
+            parts.error_handler = Some(Rc::new(f));
+        }
+        self
+    }
+
+    /// Register response format for `Negotiated` responder.
+    ///
+    /// Serializer receives response value as `erased_serde::Serialize`
+    /// trait object, that could be passed to any *serde* serializer.
+    /// Format with the same mime type as built-in format replaces it.
+    ///
+    /// ```rust
+    /// # extern crate actix_web;
+    /// #[macro_use] extern crate serde_json;
+    /// use actix_web::{App, Negotiated};
+    ///
+    /// fn main() {
+    ///     let app = App::new()
+    ///         .response_format("application/vnd.api+json".parse().unwrap(), |value| {
+    ///             Ok(serde_json::to_vec(&json!({ "data": value }))?.into())
+    ///         })
+    ///         .resource("/", |r| r.f(|_| Negotiated(vec!["a", "b"])))
+    ///         .finish();
+    /// }
+    /// ```
+    pub fn response_format<F>(mut self, mime: Mime, f: F) -> App<S>
+    where
+        F: Fn(&erased_serde::Serialize) -> Result<Binary, Error> + 'static,
+    {
+        {
+            let parts = self.parts.as_mut().expect("Use after finish");
+            parts.formats.retain(|&(ref m, _)| {
+                m.type_() != mime.type_() || m.subtype() != mime.subtype()
+            });
+            parts.formats.push((mime, Rc::new(f)));
+        }
+        self
+    }
+
     /// Register an external resource.
     ///
     /// External resources are useful for URL generation purposes only
@@ -491,9 +697,127 @@ where
         cfg(self)
     }
 
+    /// Application's route table, in order of registration.
+    ///
+    /// Table contains an entry for every route of every resource.
+    /// It is available after build with `Router::routes()`.
+    ///
+    /// ```rust
+    /// # extern crate actix_web;
+    /// use actix_web::{App, HttpResponse};
+    ///
+    /// fn main() {
+    ///     let app = App::new()
+    ///         .prefix("/app")
+    ///         .resource("/user/{id}", |r| {
+    ///             r.name("user");
+    ///             r.get().f(|_| HttpResponse::Ok());
+    ///             r.delete().f(|_| HttpResponse::Ok());
+    ///         });
+    ///
+    ///     for route in app.routes() {
+    ///         println!("{}", route); // <- "GET /app/user/{id} (user)"
+    ///     }
+    /// }
+    /// ```
+    pub fn routes(&self) -> Vec<RouteInfo> {
+        let parts = self.parts.as_ref().expect("Use after finish");
+        let mut routes = Vec::new();
+        for &(ref pattern, ref handler) in &parts.resources {
+            if let Some(ref handler) = *handler {
+                routes.extend(handler.routes_info(&parts.prefix, pattern));
+            }
+        }
+        routes
+    }
+
+    /// Resources that could never be matched, because earlier
+    /// resource matches every path they match.
+    pub fn route_conflicts(&self) -> Vec<RouteConflict> {
+        let parts = self.parts.as_ref().expect("Use after finish");
+        find_conflicts(
+            parts
+                .resources
+                .iter()
+                .filter(|&&(_, ref handler)| handler.is_some())
+                .map(|&(ref pattern, _)| pattern),
+        )
+    }
+
+    /// Set what to do with route conflicts when application is built.
+    ///
+    /// By default warning is logged for every conflict,
+    /// see `App::route_conflicts()`.
+    ///
+    /// ```rust,should_panic
+    /// # extern crate actix_web;
+    /// use actix_web::dev::ConflictPolicy;
+    /// use actix_web::{App, HttpResponse};
+    ///
+    /// fn main() {
+    ///     let app = App::new()
+    ///         .check_routes(ConflictPolicy::Panic)
+    ///         .resource("/{name}", |r| r.f(|_| HttpResponse::Ok()))
+    ///         .resource("/index.html", |r| r.f(|_| HttpResponse::Ok()))
+    ///         .finish(); // <- panics, "/index.html" is never used
+    /// }
+    /// ```
+    pub fn check_routes(mut self, policy: ConflictPolicy) -> App<S> {
+        {
+            let parts = self.parts.as_mut().expect("Use after finish");
+            parts.conflicts = policy;
+        }
+        self
+    }
+
+    /// Serve OpenAPI document of the application at a specific path.
+    ///
+    /// Document is generated from application's route table, only
+    /// routes described with `Route::doc()` are included.
+    ///
+    /// ```rust
+    /// # extern crate actix_web;
+    /// use actix_web::openapi::{OpenApi, Operation};
+    /// use actix_web::{App, HttpResponse};
+    ///
+    /// fn main() {
+    ///     let app = App::new()
+    ///         .resource("/index.html", |r| {
+    ///             r.get()
+    ///                 .doc(Operation::new().summary("Index page"))
+    ///                 .f(|_| HttpResponse::Ok())
+    ///         })
+    ///         .openapi("/openapi.json", OpenApi::new("My api", "1.0"));
+    /// }
+    /// ```
+    pub fn openapi(self, path: &str, api: OpenApi) -> App<S> {
+        self.resource(path, move |r| {
+            r.get().f(move |req: HttpRequest<S>| {
+                let routes = req.router()
+                    .map(|router| router.routes())
+                    .unwrap_or(&[]);
+                HttpResponse::Ok().json(api.document(routes))
+            })
+        })
+    }
+
     /// Finish application configuration and create `HttpHandler` object.
     pub fn finish(&mut self) -> HttpApplication<S> {
+        {
+            let parts = self.parts.as_mut().expect("Use after finish");
+            for item in &mut parts.resources {
+                let normalized = parts.policy.normalize_pattern(item.0.pattern());
+                if let Some(pattern) = normalized {
+                    let resource = Resource::new(item.0.name(), &pattern);
+                    item.0 = resource;
+                }
+            }
+        }
+        let routes = self.routes();
+        let conflicts = self.route_conflicts();
         let parts = self.parts.take().expect("Use after finish");
+        parts.conflicts.check(&conflicts);
+
         let prefix = parts.prefix.trim().trim_right_matches('/');
         let (prefix, prefix_len) = if prefix.is_empty() {
             ("/".to_owned(), 0)
@@ -506,12 +830,16 @@ where
             resources.push((pattern, None));
         }
 
-        let (router, resources) = Router::new(&prefix, parts.settings, resources);
+        let (mut router, resources) = Router::new(&prefix, parts.settings, resources);
+        router.set_error_handler(parts.error_handler);
+        router.set_response_formats(negotiate::default_formats(&parts.formats));
+        router.set_routes(routes);
 
         let inner = Rc::new(UnsafeCell::new(Inner {
             prefix: prefix_len,
             default: parts.default,
             encoding: parts.encoding,
+            policy: parts.policy,
             handlers: parts.handlers,
             resources,
         }));
@@ -520,6 +848,7 @@ where
             state: Rc::new(parts.state),
             router: router.clone(),
             middlewares: Rc::new(parts.middlewares),
+            hosts: parts.hosts,
             prefix,
             prefix_len,
             inner,
@@ -601,9 +930,11 @@ impl<S: 'static> Iterator for App<S> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use http::StatusCode;
+    use http::{header, StatusCode};
     use httprequest::HttpRequest;
     use httpresponse::HttpResponse;
+    use openapi::Operation;
+    use serde_json::{self, Value};
     use test::TestRequest;
 
     #[test]
@@ -677,6 +1008,155 @@ mod tests {
         assert!(resp.is_err());
     }
 
+    #[test]
+    fn test_host() {
+        let mut app = App::new()
+            .host("example.com")
+            .host("{user}.example.com")
+            .resource("/", |r| r.f(|_| HttpResponse::Ok()))
+            .finish();
+
+        let req = TestRequest::with_header("host", "example.com:8080").finish();
+        assert!(app.handle(req).is_ok());
+
+        let req = TestRequest::with_header("host", "other.com").finish();
+        assert!(app.handle(req).is_err());
+
+        let mut req = TestRequest::with_header("host", "user1.example.com").finish();
+        let mut app2 = App::new().host("*.example.com").finish();
+        assert!(app2.handle(req.clone()).is_ok());
+        assert!(app.hosts.iter().any(|host| host.check(&mut req)));
+        assert_eq!(req.match_info().get("user"), Some("user1"));
+    }
+
+    #[test]
+    fn test_method_not_allowed() {
+        let mut app = App::new()
+            .resource("/test", |r| {
+                r.get().f(|_| HttpResponse::Ok());
+                r.route()
+                    .filter(pred::Any(pred::Put()).or(pred::Post()))
+                    .f(|_| HttpResponse::Created());
+            })
+            .resource("/any", |r| {
+                r.get().f(|_| HttpResponse::Ok());
+                r.route()
+                    .filter(pred::Header("x-test", "1"))
+                    .f(|_| HttpResponse::Ok());
+            })
+            .finish();
+
+        let req = TestRequest::with_uri("/test").method(Method::POST).finish();
+        let resp = app.run(req);
+        assert_eq!(resp.as_response().unwrap().status(), StatusCode::CREATED);
+
+        let req = TestRequest::with_uri("/test").method(Method::DELETE).finish();
+        let resp = app.run(req);
+        let resp = resp.as_response().unwrap();
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(
+            resp.headers().get(header::ALLOW).unwrap(),
+            "GET, PUT, POST, HEAD, OPTIONS"
+        );
+
+        let req = TestRequest::with_uri("/test").method(Method::OPTIONS).finish();
+        let resp = app.run(req);
+        let resp = resp.as_response().unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::ALLOW).unwrap(),
+            "GET, PUT, POST, HEAD, OPTIONS"
+        );
+
+        let req = TestRequest::with_uri("/test").method(Method::HEAD).finish();
+        let resp = app.run(req);
+        assert_eq!(resp.as_response().unwrap().status(), StatusCode::OK);
+
+        let req = TestRequest::with_uri("/any").method(Method::DELETE).finish();
+        let resp = app.run(req);
+        assert_eq!(
+            resp.as_response().unwrap().status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn test_routes() {
+        let mut app = App::new()
+            .prefix("/app")
+            .resource("/user/{id}", |r| {
+                r.name("user");
+                r.get().f(|_| HttpResponse::Ok());
+                r.route()
+                    .filter(pred::Any(pred::Put()).or(pred::Post()))
+                    .filter(pred::Header("x-api", "1"))
+                    .f(|_| HttpResponse::Ok());
+            })
+            .resource("/{name}", |r| r.f(|_| HttpResponse::Ok()))
+            .resource("/index.html", |r| r.f(|_| HttpResponse::Ok()));
+
+        let routes: Vec<_> = app.routes().iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            routes,
+            vec![
+                "GET /app/user/{id} (user)",
+                "PUT,POST /app/user/{id} (user) [(PUT or POST), header(x-api: 1)]",
+                "* /app/{name}",
+                "* /app/index.html",
+            ]
+        );
+        assert_eq!(
+            app.route_conflicts(),
+            vec![RouteConflict::Shadowed(
+                "/index.html".to_owned(),
+                "/{name}".to_owned(),
+            )]
+        );
+
+        let app = app.finish();
+        assert_eq!(app.router.routes().len(), 4);
+        assert_eq!(app.router.routes()[0].methods(), Some(&[Method::GET][..]));
+        assert_eq!(app.router.conflicts().len(), 1);
+    }
+
+    #[test]
+    fn test_openapi() {
+        let mut app = App::new()
+            .prefix("/app")
+            .resource("/item/{id}", |r| {
+                r.get()
+                    .doc(Operation::new().summary("Get item"))
+                    .f(|_| HttpResponse::Ok());
+                r.post().f(|_| HttpResponse::Ok());
+            })
+            .openapi("/openapi.json", OpenApi::new("Test", "1.0"))
+            .finish();
+
+        let req = TestRequest::with_uri("/app/openapi.json").finish();
+        let req = app.prepare_request(req);
+        let resp = app.run(req);
+        let resp = resp.as_response().unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let doc: Value = serde_json::from_slice(resp.body().binary().unwrap().as_ref())
+            .unwrap();
+        assert_eq!(doc["info"]["title"], "Test");
+        let paths = doc["paths"].as_object().unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths["/app/item/{id}"]["get"]["summary"], "Get item");
+        assert!(paths["/app/item/{id}"].get("post").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "is shadowed by resource")]
+    fn test_routes_conflict_panic() {
+        App::new()
+            .check_routes(ConflictPolicy::Panic)
+            .resource("/{name}", |r| r.f(|_| HttpResponse::Ok()))
+            .resource("/index.html", |r| r.f(|_| HttpResponse::Ok()))
+            .finish();
+    }
+
     #[test]
     fn test_handler() {
         let mut app = App::new()
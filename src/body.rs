@@ -6,6 +6,7 @@ use std::{fmt, mem};
 
 use context::ActorHttpContext;
 use error::Error;
+use fs::ChunkedReadFile;
 use handler::Responder;
 use httprequest::HttpRequest;
 use httpresponse::HttpResponse;
@@ -24,6 +25,10 @@ pub enum Body {
     Streaming(BodyStream),
     /// Special body type for actor response.
     Actor(Box<ActorHttpContext>),
+    /// File body. On plain *HTTP/1* connections file content is sent
+    /// with `sendfile(2)` if possible, otherwise it is read chunk-by-chunk
+    /// like streaming body.
+    File(ChunkedReadFile),
 }
 
 /// Represents various types of binary body.
@@ -48,7 +53,7 @@ impl Body {
     #[inline]
     pub fn is_streaming(&self) -> bool {
         match *self {
-            Body::Streaming(_) | Body::Actor(_) => true,
+            Body::Streaming(_) | Body::Actor(_) | Body::File(_) => true,
             _ => false,
         }
     }
@@ -79,7 +84,7 @@ impl PartialEq for Body {
                 Body::Binary(ref b2) => b == b2,
                 _ => false,
             },
-            Body::Streaming(_) | Body::Actor(_) => false,
+            Body::Streaming(_) | Body::Actor(_) | Body::File(_) => false,
         }
     }
 }
@@ -91,6 +96,7 @@ impl fmt::Debug for Body {
             Body::Binary(ref b) => write!(f, "Body::Binary({:?})", b),
             Body::Streaming(_) => write!(f, "Body::Streaming(_)"),
             Body::Actor(_) => write!(f, "Body::Actor(_)"),
+            Body::File(_) => write!(f, "Body::File(_)"),
         }
     }
 }
@@ -110,6 +116,12 @@ impl From<Box<ActorHttpContext>> for Body {
     }
 }
 
+impl From<ChunkedReadFile> for Body {
+    fn from(file: ChunkedReadFile) -> Body {
+        Body::File(file)
+    }
+}
+
 impl Binary {
     #[inline]
     pub fn is_empty(&self) -> bool {
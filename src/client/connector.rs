@@ -5,7 +5,7 @@ use std::rc::Rc;
 use std::time::{Duration, Instant};
 use std::{fmt, io, mem, time};
 
-use actix::actors::{Connect as ResolveConnect, Connector, ConnectorError};
+use actix::actors::ConnectorError;
 use actix::fut::WrapFuture;
 use actix::registry::ArbiterService;
 use actix::{fut, Actor, ActorFuture, ActorResponse, Arbiter, AsyncContext, Context,
@@ -28,6 +28,8 @@ use native_tls::{Error as TlsError, TlsConnector};
 #[cfg(all(feature = "tls", not(feature = "alpn")))]
 use tokio_tls::TlsConnectorExt;
 
+use super::cookies::CookieJar;
+use super::resolver::{CachedResolver, Resolver};
 use server::IoStream;
 use {HAS_OPENSSL, HAS_TLS};
 
@@ -42,6 +44,281 @@ pub struct ClientConnectorStats {
     pub timeouts: usize,
 }
 
+/// Request snapshot of the `ClientConnector` connection pool
+///
+/// ```rust
+/// # extern crate actix;
+/// # extern crate actix_web;
+/// # extern crate futures;
+/// # use futures::Future;
+/// use actix::prelude::*;
+/// use actix_web::client::{ClientConnector, PoolStatus};
+///
+/// fn main() {
+///     let sys = System::new("test");
+///     let conn: Addr<Unsync, _> = ClientConnector::default().start();
+///
+///     Arbiter::handle().spawn({
+///         conn.send(PoolStatus)
+///             .map_err(|_| ())
+///             .and_then(|res| {
+///                 let snapshot = res.unwrap();
+///                 for host in snapshot.hosts() {
+///                     println!("{}:{} idle: {} active: {} waiting: {}",
+///                              host.host(), host.port(),
+///                              host.idle(), host.active(), host.waiting());
+///                 }
+/// #               Arbiter::system().do_send(actix::msgs::SystemExit(0));
+///                 Ok(())
+///             })
+///     });
+///
+///     sys.run();
+/// }
+/// ```
+pub struct PoolStatus;
+
+impl Message for PoolStatus {
+    type Result = Result<PoolSnapshot, ()>;
+}
+
+/// Upper bounds of wait time histogram buckets, in milliseconds
+const WAIT_BUCKETS: [u64; 11] = [1, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+/// Histogram of time spent by connect requests waiting for
+/// an available connection
+#[derive(Clone, Debug, Default)]
+pub struct WaitHistogram {
+    counts: [u64; 12],
+    count: u64,
+    sum: Duration,
+    max: Duration,
+}
+
+impl WaitHistogram {
+    fn record(&mut self, wait: Duration) {
+        let ms = wait.as_secs() * 1000 + u64::from(wait.subsec_nanos() / 1_000_000);
+        let idx = WAIT_BUCKETS
+            .iter()
+            .position(|bound| ms <= *bound)
+            .unwrap_or(WAIT_BUCKETS.len());
+        self.counts[idx] += 1;
+        self.count += 1;
+        self.sum += wait;
+        if wait > self.max {
+            self.max = wait;
+        }
+    }
+
+    fn merge(&mut self, other: &WaitHistogram) {
+        for (count, other) in self.counts.iter_mut().zip(other.counts.iter()) {
+            *count += *other;
+        }
+        self.count += other.count;
+        self.sum += other.sum;
+        if other.max > self.max {
+            self.max = other.max;
+        }
+    }
+
+    /// Histogram buckets, pairs of bucket upper bound and number of waits
+    /// that fall into the bucket. Last bucket has no upper bound.
+    ///
+    /// Bucket bounds are 1, 5, 10, 25, 50, 100, 250, 500 milliseconds
+    /// and 1, 2.5, 5 seconds.
+    pub fn buckets(&self) -> Vec<(Option<Duration>, u64)> {
+        WAIT_BUCKETS
+            .iter()
+            .map(|ms| Some(Duration::from_millis(*ms)))
+            .chain(Some(None))
+            .zip(self.counts.iter().cloned())
+            .collect()
+    }
+
+    /// Total number of recorded waits
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Sum of all recorded wait times
+    pub fn sum(&self) -> Duration {
+        self.sum
+    }
+
+    /// Longest recorded wait time
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+}
+
+/// Idle connection stored in the pool
+#[derive(Clone, Debug)]
+pub struct IdleConnection {
+    age: Duration,
+    idle: Duration,
+}
+
+impl IdleConnection {
+    /// Time since the connection has been opened
+    pub fn age(&self) -> Duration {
+        self.age
+    }
+
+    /// Time since the connection has been returned to the pool
+    pub fn idle(&self) -> Duration {
+        self.idle
+    }
+}
+
+/// Connection acquired from the pool
+#[derive(Clone, Debug)]
+pub struct ActiveConnection {
+    age: Duration,
+}
+
+impl ActiveConnection {
+    /// Time since the connection has been opened
+    pub fn age(&self) -> Duration {
+        self.age
+    }
+}
+
+/// Connection pool state of a single endpoint
+#[derive(Clone, Debug)]
+pub struct HostSnapshot {
+    host: String,
+    port: u16,
+    ssl: bool,
+    active: usize,
+    waiting: usize,
+    opened: Vec<ActiveConnection>,
+    idle: Vec<IdleConnection>,
+    wait_times: WaitHistogram,
+}
+
+impl HostSnapshot {
+    /// Endpoint host name
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Endpoint port
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Is endpoint secure
+    pub fn ssl(&self) -> bool {
+        self.ssl
+    }
+
+    /// Number of acquired connections, including connections
+    /// that are being established
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    /// Number of idle connections
+    pub fn idle(&self) -> usize {
+        self.idle.len()
+    }
+
+    /// Number of connect requests waiting for available connection
+    pub fn waiting(&self) -> usize {
+        self.waiting
+    }
+
+    /// Idle connections, oldest returned to the pool first
+    pub fn idle_connections(&self) -> &[IdleConnection] {
+        &self.idle
+    }
+
+    /// Established acquired connections, oldest first. Connections
+    /// that are being established are not included.
+    pub fn active_connections(&self) -> &[ActiveConnection] {
+        &self.opened
+    }
+
+    /// Wait times of connect requests to this endpoint. Requests that
+    /// acquire connection immediately are recorded with zero wait time.
+    ///
+    /// Wait times are kept while endpoint has acquired or idle connections
+    /// or waiting requests, history is reset once all of them are gone.
+    pub fn wait_times(&self) -> &WaitHistogram {
+        &self.wait_times
+    }
+}
+
+/// Snapshot of the `ClientConnector` connection pool
+#[derive(Clone, Debug)]
+pub struct PoolSnapshot {
+    limit: usize,
+    limit_per_host: usize,
+    paused: bool,
+    closing: usize,
+    hosts: Vec<HostSnapshot>,
+}
+
+impl PoolSnapshot {
+    /// Total connections limit, 0 means no limit
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Per host connections limit, 0 means no limit
+    pub fn limit_per_host(&self) -> usize {
+        self.limit_per_host
+    }
+
+    /// Is connector paused
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Number of connections that are being shut down
+    pub fn closing(&self) -> usize {
+        self.closing
+    }
+
+    /// Per endpoint pool state
+    pub fn hosts(&self) -> &[HostSnapshot] {
+        &self.hosts
+    }
+
+    /// Pool state of the endpoint
+    pub fn host(&self, host: &str, port: u16) -> Option<&HostSnapshot> {
+        self.hosts
+            .iter()
+            .find(|h| h.host == host && h.port == port)
+    }
+
+    /// Total number of acquired connections
+    pub fn active(&self) -> usize {
+        self.hosts.iter().map(|h| h.active).sum()
+    }
+
+    /// Total number of idle connections
+    pub fn idle(&self) -> usize {
+        self.hosts.iter().map(|h| h.idle()).sum()
+    }
+
+    /// Total number of waiting connect requests
+    pub fn waiting(&self) -> usize {
+        self.hosts.iter().map(|h| h.waiting).sum()
+    }
+
+    /// Wait times of connect requests to all endpoints in the snapshot.
+    /// Requests that acquire connection immediately are recorded
+    /// with zero wait time.
+    pub fn wait_times(&self) -> WaitHistogram {
+        let mut hist = WaitHistogram::default();
+        for host in &self.hosts {
+            hist.merge(&host.wait_times);
+        }
+        hist
+    }
+}
+
 #[derive(Debug)]
 /// `Connect` type represents a message that can be sent to
 /// `ClientConnector` with a connection request.
@@ -161,6 +438,7 @@ impl From<ConnectorError> for ClientConnectorError {
 
 struct Waiter {
     tx: oneshot::Sender<Result<Connection, ClientConnectorError>>,
+    start: Instant,
     wait: Instant,
     conn_timeout: Duration,
 }
@@ -175,6 +453,9 @@ pub struct ClientConnector {
 
     stats: ClientConnectorStats,
     subscriber: Option<Recipient<Syn, ClientConnectorStats>>,
+    cookies: Option<CookieJar>,
+    resolver: CachedResolver,
+    attempt_delay: Duration,
 
     pool: Rc<Pool>,
     pool_modified: Rc<Cell<bool>>,
@@ -188,6 +469,7 @@ pub struct ClientConnector {
     available: HashMap<Key, VecDeque<Conn>>,
     to_close: Vec<Connection>,
     waiters: HashMap<Key, VecDeque<Waiter>>,
+    wait_times: HashMap<Key, WaitHistogram>,
     wait_timeout: Option<(Instant, Timeout)>,
     paused: Option<Option<(Instant, Timeout)>>,
 }
@@ -220,6 +502,9 @@ impl Default for ClientConnector {
             ClientConnector {
                 stats: ClientConnectorStats::default(),
                 subscriber: None,
+                cookies: None,
+                resolver: CachedResolver::default(),
+                attempt_delay: Duration::from_millis(250),
                 pool: Rc::new(Pool::new(Rc::clone(&_modified))),
                 pool_modified: _modified,
                 connector: builder.build().unwrap(),
@@ -232,6 +517,7 @@ impl Default for ClientConnector {
                 available: HashMap::new(),
                 to_close: Vec::new(),
                 waiters: HashMap::new(),
+                wait_times: HashMap::new(),
                 wait_timeout: None,
                 paused: None,
             }
@@ -241,6 +527,9 @@ impl Default for ClientConnector {
         ClientConnector {
             stats: ClientConnectorStats::default(),
             subscriber: None,
+            cookies: None,
+            resolver: CachedResolver::default(),
+            attempt_delay: Duration::from_millis(250),
             pool: Rc::new(Pool::new(Rc::clone(&_modified))),
             pool_modified: _modified,
             conn_lifetime: Duration::from_secs(15),
@@ -252,6 +541,7 @@ impl Default for ClientConnector {
             available: HashMap::new(),
             to_close: Vec::new(),
             waiters: HashMap::new(),
+            wait_times: HashMap::new(),
             wait_timeout: None,
             paused: None,
         }
@@ -308,6 +598,9 @@ impl ClientConnector {
             connector,
             stats: ClientConnectorStats::default(),
             subscriber: None,
+            cookies: None,
+            resolver: CachedResolver::default(),
+            attempt_delay: Duration::from_millis(250),
             pool: Rc::new(Pool::new(Rc::clone(&modified))),
             pool_modified: modified,
             conn_lifetime: Duration::from_secs(75),
@@ -319,6 +612,7 @@ impl ClientConnector {
             available: HashMap::new(),
             to_close: Vec::new(),
             waiters: HashMap::new(),
+            wait_times: HashMap::new(),
             wait_timeout: None,
             paused: None,
         }
@@ -367,6 +661,43 @@ impl ClientConnector {
         self
     }
 
+    /// Use custom host name resolver.
+    ///
+    /// By default `SystemResolver` is used.
+    pub fn resolver<R: Resolver>(mut self, resolver: R) -> Self {
+        self.resolver.set_resolver(resolver);
+        self
+    }
+
+    /// Limit lifetime of cached addresses to `ttl` period.
+    ///
+    /// Resolved addresses are cached until validity reported by
+    /// `Resolver::lookup()` ends, for `SystemResolver` it is TTL
+    /// of DNS records. `ttl` caps that period, results of resolvers
+    /// that do not report validity are cached for `ttl` period.
+    /// Zero `ttl` disables caching.
+    pub fn dns_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.resolver.set_ttl(Some(ttl));
+        self
+    }
+
+    /// Set delay between connection attempts to different addresses
+    /// of the same host (happy eyeballs). The default delay is 250 milliseconds.
+    ///
+    /// Next address is tried immediately if previous attempt fails.
+    pub fn connection_attempt_delay(mut self, delay: Duration) -> Self {
+        self.attempt_delay = delay;
+        self
+    }
+
+    /// Use cookie jar for all requests sent through this connector.
+    ///
+    /// Jar set with `ClientRequestBuilder::cookie_jar()` takes precedence.
+    pub fn cookie_jar(mut self, jar: CookieJar) -> Self {
+        self.cookies = Some(jar);
+        self
+    }
+
     fn acquire(&mut self, key: &Key) -> Acquire {
         // check limits
         if self.limit > 0 {
@@ -508,6 +839,17 @@ impl ClientConnector {
                     }
                 }
             }
+
+            // drop state of endpoints without connections and waiters
+            self.available.retain(|_, conns| !conns.is_empty());
+            self.waiters.retain(|_, waiters| !waiters.is_empty());
+            let acquired = &self.acquired_per_host;
+            let available = &self.available;
+            let waiters = &self.waiters;
+            self.wait_times.retain(|key, _| {
+                acquired.contains_key(key) || available.contains_key(key)
+                    || waiters.contains_key(key)
+            });
         }
 
         self.pool_modified.set(false);
@@ -531,12 +873,16 @@ impl ClientConnector {
         let now = Instant::now();
         let mut next = None;
 
-        for waiters in self.waiters.values_mut() {
+        for (key, waiters) in &mut self.waiters {
             let mut idx = 0;
             while idx < waiters.len() {
                 if waiters[idx].wait <= now {
                     self.stats.timeouts += 1;
                     let waiter = waiters.swap_remove_back(idx).unwrap();
+                    self.wait_times
+                        .entry(key.clone())
+                        .or_insert_with(WaitHistogram::default)
+                        .record(now - waiter.start);
                     let _ = waiter.tx.send(Err(ClientConnectorError::Timeout));
                 } else {
                     if let Some(n) = next {
@@ -575,11 +921,13 @@ impl ClientConnector {
         // connection is not available, wait
         let (tx, rx) = oneshot::channel();
 
-        let wait = Instant::now() + wait;
+        let start = Instant::now();
+        let wait = start + wait;
         self.install_wait_timeout(wait);
 
         let waiter = Waiter {
             tx,
+            start,
             wait,
             conn_timeout,
         };
@@ -589,6 +937,74 @@ impl ClientConnector {
             .push_back(waiter);
         rx
     }
+
+    fn record_wait(&mut self, key: &Key, wait: Duration) {
+        self.wait_times
+            .entry(key.clone())
+            .or_insert_with(WaitHistogram::default)
+            .record(wait);
+    }
+
+    fn snapshot(&mut self) -> PoolSnapshot {
+        if self.pool_modified.get() {
+            self.collect(false);
+        }
+
+        let mut keys: Vec<&Key> = self.acquired_per_host.keys().collect();
+        keys.extend(self.available.keys());
+        keys.extend(self.waiters.keys());
+        keys.extend(self.wait_times.keys());
+        keys.sort_by(|a, b| (&a.host, a.port, a.ssl).cmp(&(&b.host, b.port, b.ssl)));
+        keys.dedup();
+
+        let now = Instant::now();
+        let hosts = keys.into_iter()
+            .map(|key| HostSnapshot {
+                host: key.host.clone(),
+                port: key.port,
+                ssl: key.ssl,
+                active: self.acquired_per_host.get(key).cloned().unwrap_or(0),
+                waiting: self.waiters
+                    .get(key)
+                    .map(|w| w.iter().filter(|w| !w.tx.is_canceled()).count())
+                    .unwrap_or(0),
+                opened: self.pool.opened(key, now),
+                idle: self.available
+                    .get(key)
+                    .map(|conns| {
+                        conns
+                            .iter()
+                            .map(|conn| IdleConnection {
+                                age: now - conn.1.ts,
+                                idle: now - conn.0,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_else(Vec::new),
+                wait_times: self.wait_times.get(key).cloned().unwrap_or_default(),
+            })
+            .filter(|host| {
+                host.active > 0 || host.waiting > 0 || !host.idle.is_empty()
+                    || host.wait_times.count > 0
+            })
+            .collect();
+
+        PoolSnapshot {
+            hosts,
+            limit: self.limit,
+            limit_per_host: self.limit_per_host,
+            paused: self.paused.is_some(),
+            closing: self.to_close.len(),
+        }
+    }
+}
+
+impl Handler<PoolStatus> for ClientConnector {
+    type Result = Result<PoolSnapshot, ()>;
+
+    fn handle(&mut self, _: PoolStatus, _: &mut Self::Context) -> Self::Result {
+        Ok(self.snapshot())
+    }
 }
 
 impl Handler<Pause> for ClientConnector {
@@ -667,8 +1083,8 @@ impl Handler<Connect> for ClientConnector {
             return ActorResponse::async(
                 rx.map_err(|_| ClientConnectorError::Disconnected)
                     .into_actor(self)
-                    .and_then(|res, _, _| match res {
-                        Ok(conn) => fut::ok(conn),
+                    .and_then(|res, act, _| match res {
+                        Ok(conn) => fut::ok(conn.cookie_jar(act.cookies.clone())),
                         Err(err) => fut::err(err),
                     }),
             );
@@ -679,9 +1095,15 @@ impl Handler<Connect> for ClientConnector {
             match self.acquire(&key) {
                 Acquire::Acquired(mut conn) => {
                     // use existing connection
-                    conn.pool = Some(AcquiredConn(key, Some(Rc::clone(&self.pool))));
+                    self.record_wait(&key, Duration::from_secs(0));
+                    let mut acquired =
+                        AcquiredConn::new(key, Some(Rc::clone(&self.pool)));
+                    acquired.activate(conn.ts);
+                    conn.pool = Some(acquired);
                     self.stats.reused += 1;
-                    return ActorResponse::async(fut::ok(conn));
+                    return ActorResponse::async(fut::ok(
+                        conn.cookie_jar(self.cookies.clone()),
+                    ));
                 }
                 Acquire::NotAvailable => {
                     // connection is not available, wait
@@ -690,8 +1112,10 @@ impl Handler<Connect> for ClientConnector {
                     return ActorResponse::async(
                         rx.map_err(|_| ClientConnectorError::Disconnected)
                             .into_actor(self)
-                            .and_then(|res, _, _| match res {
-                                Ok(conn) => fut::ok(conn),
+                            .and_then(|res, act, _| match res {
+                                Ok(conn) => {
+                                    fut::ok(conn.cookie_jar(act.cookies.clone()))
+                                }
                                 Err(err) => fut::err(err),
                             }),
                     );
@@ -701,15 +1125,14 @@ impl Handler<Connect> for ClientConnector {
         } else {
             None
         };
-        let conn = AcquiredConn(key, pool);
+        self.record_wait(&key, Duration::from_secs(0));
+        let conn = AcquiredConn::new(key, pool);
 
         {
             ActorResponse::async(
-                Connector::from_registry()
-                    .send(
-                        ResolveConnect::host_and_port(&conn.0.host, port)
-                            .timeout(conn_timeout),
-                    )
+                self.resolver
+                    .connect(&conn.0.host, port, conn_timeout, self.attempt_delay)
+                    .then(Ok::<_, ()>)
                     .into_actor(self)
                     .map_err(|_, _, _| ClientConnectorError::Disconnected)
                     .and_then(move |res, act, _| {
@@ -796,7 +1219,8 @@ impl Handler<Connect> for ClientConnector {
                                 }
                             }
                         }
-                    }),
+                    })
+                    .map(|conn, act, _| conn.cookie_jar(act.cookies.clone())),
             )
         }
     }
@@ -843,8 +1267,11 @@ impl fut::ActorFuture for Maintenance {
                     Acquire::Acquired(mut conn) => {
                         // use existing connection
                         act.stats.reused += 1;
-                        conn.pool =
-                            Some(AcquiredConn(key.clone(), Some(Rc::clone(&act.pool))));
+                        act.record_wait(key, Instant::now() - waiter.start);
+                        let mut acquired =
+                            AcquiredConn::new(key.clone(), Some(Rc::clone(&act.pool)));
+                        acquired.activate(conn.ts);
+                        conn.pool = Some(acquired);
                         let _ = waiter.tx.send(Ok(conn));
                     }
                     Acquire::NotAvailable => {
@@ -852,13 +1279,19 @@ impl fut::ActorFuture for Maintenance {
                         break;
                     }
                     Acquire::Available => {
-                        let conn = AcquiredConn(key.clone(), Some(Rc::clone(&act.pool)));
+                        act.record_wait(key, Instant::now() - waiter.start);
+                        let conn =
+                            AcquiredConn::new(key.clone(), Some(Rc::clone(&act.pool)));
 
                         fut::WrapFuture::<ClientConnector>::actfuture(
-                            Connector::from_registry().send(
-                                ResolveConnect::host_and_port(&conn.0.host, conn.0.port)
-                                    .timeout(waiter.conn_timeout),
-                            ),
+                            act.resolver
+                                .connect(
+                                    &conn.0.host,
+                                    conn.0.port,
+                                    waiter.conn_timeout,
+                                    act.attempt_delay,
+                                )
+                                .then(Ok::<_, ()>),
                         ).map_err(|_, _, _| ())
                             .and_then(move |res, act, _| {
                                 #[cfg_attr(rustfmt, rustfmt_skip)]
@@ -1051,16 +1484,34 @@ enum Acquire {
     NotAvailable,
 }
 
-struct AcquiredConn(Key, Option<Rc<Pool>>);
+/// Reserved pool slot, open time of the connection is set
+/// once connection is established
+struct AcquiredConn(Key, Option<Rc<Pool>>, Option<Instant>);
 
 impl AcquiredConn {
+    fn new(key: Key, pool: Option<Rc<Pool>>) -> AcquiredConn {
+        AcquiredConn(key, pool, None)
+    }
+    fn activate(&mut self, ts: Instant) {
+        if let Some(ref pool) = self.1 {
+            pool.activate(&self.0, ts);
+            self.2 = Some(ts);
+        }
+    }
+    fn deactivate(&mut self, pool: &Pool) {
+        if let Some(ts) = self.2.take() {
+            pool.deactivate(&self.0, ts);
+        }
+    }
     fn close(&mut self, conn: Connection) {
         if let Some(pool) = self.1.take() {
+            self.deactivate(&pool);
             pool.close(conn);
         }
     }
     fn release(&mut self, conn: Connection) {
         if let Some(pool) = self.1.take() {
+            self.deactivate(&pool);
             pool.release(conn);
         }
     }
@@ -1069,6 +1520,7 @@ impl AcquiredConn {
 impl Drop for AcquiredConn {
     fn drop(&mut self) {
         if let Some(pool) = self.1.take() {
+            self.deactivate(&pool);
             pool.release_key(self.0.clone());
         }
     }
@@ -1076,6 +1528,7 @@ impl Drop for AcquiredConn {
 
 pub struct Pool {
     keys: RefCell<Vec<Key>>,
+    active: RefCell<HashMap<Key, Vec<Instant>>>,
     to_close: RefCell<Vec<Connection>>,
     to_release: RefCell<Vec<Connection>>,
     task: RefCell<Option<Task>>,
@@ -1087,12 +1540,50 @@ impl Pool {
         Pool {
             modified,
             keys: RefCell::new(Vec::new()),
+            active: RefCell::new(HashMap::new()),
             to_close: RefCell::new(Vec::new()),
             to_release: RefCell::new(Vec::new()),
             task: RefCell::new(None),
         }
     }
 
+    fn activate(&self, key: &Key, ts: Instant) {
+        self.active
+            .borrow_mut()
+            .entry(key.clone())
+            .or_insert_with(Vec::new)
+            .push(ts);
+    }
+
+    fn deactivate(&self, key: &Key, ts: Instant) {
+        let mut active = self.active.borrow_mut();
+        let empty = if let Some(conns) = active.get_mut(key) {
+            if let Some(idx) = conns.iter().position(|t| *t == ts) {
+                conns.swap_remove(idx);
+            }
+            conns.is_empty()
+        } else {
+            false
+        };
+        if empty {
+            active.remove(key);
+        }
+    }
+
+    /// Ages of established acquired connections, oldest first
+    fn opened(&self, key: &Key, now: Instant) -> Vec<ActiveConnection> {
+        let mut opened: Vec<_> = self.active
+            .borrow()
+            .get(key)
+            .cloned()
+            .unwrap_or_else(Vec::new);
+        opened.sort();
+        opened
+            .into_iter()
+            .map(|ts| ActiveConnection { age: now - ts })
+            .collect()
+    }
+
     fn collect_keys(&self) -> Option<Vec<Key>> {
         if self.keys.borrow().is_empty() {
             None
@@ -1152,6 +1643,7 @@ pub struct Connection {
     key: Key,
     stream: Box<IoStream>,
     pool: Option<AcquiredConn>,
+    cookies: Option<CookieJar>,
     ts: Instant,
 }
 
@@ -1162,15 +1654,30 @@ impl fmt::Debug for Connection {
 }
 
 impl Connection {
-    fn new(key: Key, pool: Option<AcquiredConn>, stream: Box<IoStream>) -> Self {
+    fn new(key: Key, mut pool: Option<AcquiredConn>, stream: Box<IoStream>) -> Self {
+        let ts = Instant::now();
+        if let Some(ref mut pool) = pool {
+            pool.activate(ts);
+        }
         Connection {
             key,
             stream,
             pool,
-            ts: Instant::now(),
+            cookies: None,
+            ts,
         }
     }
 
+    fn cookie_jar(mut self, jar: Option<CookieJar>) -> Self {
+        self.cookies = jar;
+        self
+    }
+
+    /// Cookie jar of the connector this connection is acquired from
+    pub(crate) fn cookies(&self) -> Option<&CookieJar> {
+        self.cookies.as_ref()
+    }
+
     pub fn stream(&mut self) -> &mut IoStream {
         &mut *self.stream
     }
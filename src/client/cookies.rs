@@ -0,0 +1,431 @@
+use std::cell::RefCell;
+use std::io::{self, BufRead, Write};
+use std::net::IpAddr;
+use std::rc::Rc;
+use std::str;
+
+use cookie::Cookie;
+use http::header::{self, HeaderMap, HeaderValue};
+use http::Uri;
+use percent_encoding::{percent_decode, percent_encode, USERINFO_ENCODE_SET};
+use time;
+
+/// Cookie storage shared across client requests.
+///
+/// Jar stores cookies received with `Set-Cookie` response headers and
+/// sends matching cookies with subsequent requests, following domain,
+/// path, secure and expiry rules of
+/// [RFC 6265](https://tools.ietf.org/html/rfc6265). Jar could be attached
+/// to a `ClientConnector`, in that case it is used for all requests sent
+/// through that connector, or to a single request with
+/// `ClientRequestBuilder::cookie_jar()`.
+///
+/// `CookieJar` is a reference counted handle, clones share the same storage.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::client::{self, CookieJar};
+///
+/// fn main() {
+///     let jar = CookieJar::new();
+///
+///     let req = client::get("http://www.rust-lang.org")
+///         .cookie_jar(&jar)
+///         .finish()
+///         .unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CookieJar {
+    inner: Rc<RefCell<Vec<StoredCookie>>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct StoredCookie {
+    name: String,
+    value: String,
+    domain: String,
+    host_only: bool,
+    path: String,
+    secure: bool,
+    /// Expiry time in seconds since the epoch, `None` for session cookies
+    expires: Option<i64>,
+}
+
+impl StoredCookie {
+    fn is_expired(&self, now: i64) -> bool {
+        match self.expires {
+            Some(expires) => expires <= now,
+            None => false,
+        }
+    }
+
+    fn matches(&self, host: &str, path: &str, secure: bool) -> bool {
+        if self.secure && !secure {
+            return false;
+        }
+        let domain_match = if self.host_only {
+            host == self.domain
+        } else {
+            domain_match(host, &self.domain)
+        };
+        domain_match && path_match(path, &self.path)
+    }
+}
+
+/// Domain matching, RFC 6265 section 5.1.3
+fn domain_match(host: &str, domain: &str) -> bool {
+    host == domain
+        || (host.ends_with(domain) && host[..host.len() - domain.len()].ends_with('.'))
+}
+
+/// Path matching, RFC 6265 section 5.1.4
+fn path_match(path: &str, cookie_path: &str) -> bool {
+    path == cookie_path
+        || (path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || path[cookie_path.len()..].starts_with('/')))
+}
+
+/// Default cookie path, RFC 6265 section 5.1.4
+fn default_path(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(0) | None => "/",
+        Some(idx) => &path[..idx],
+    }
+}
+
+/// Check if host is an ip address literal
+fn is_ip_literal(host: &str) -> bool {
+    host.starts_with('[') || host.parse::<IpAddr>().is_ok()
+}
+
+fn is_secure(uri: &Uri) -> bool {
+    match uri.scheme_part().map(|s| s.as_str()) {
+        Some("https") | Some("wss") => true,
+        _ => false,
+    }
+}
+
+impl CookieJar {
+    /// Create new empty cookie jar
+    pub fn new() -> CookieJar {
+        CookieJar::default()
+    }
+
+    /// Number of stored cookies
+    pub fn len(&self) -> usize {
+        self.inner.borrow().len()
+    }
+
+    /// Check if jar is empty
+    pub fn is_empty(&self) -> bool {
+        self.inner.borrow().is_empty()
+    }
+
+    /// Remove all cookies
+    pub fn clear(&self) {
+        self.inner.borrow_mut().clear()
+    }
+
+    /// Store cookie received from the `uri`.
+    ///
+    /// Cookie is ignored if its `Domain` attribute does not match
+    /// uri's host. `Domain` attribute that names a top level domain
+    /// or that is sent by an ip address host is accepted only if it equals
+    /// the host, such cookie is stored as host-only.
+    pub fn insert(&self, uri: &Uri, cookie: &Cookie) {
+        let host = match uri.host() {
+            Some(host) => host.to_lowercase(),
+            None => return,
+        };
+        let now = time::get_time().sec;
+
+        let (domain, host_only) = match cookie.domain() {
+            Some(domain) if !domain.trim_left_matches('.').is_empty() => {
+                let domain = domain.trim_left_matches('.').to_lowercase();
+                if domain == host {
+                    (domain, is_ip_literal(&host) || !host.contains('.'))
+                } else if is_ip_literal(&host) || !domain.contains('.')
+                    || !domain_match(&host, &domain)
+                {
+                    return;
+                } else {
+                    (domain, false)
+                }
+            }
+            _ => (host, true),
+        };
+        let path = match cookie.path() {
+            Some(path) if path.starts_with('/') => path.to_owned(),
+            _ => default_path(uri.path()).to_owned(),
+        };
+        let expires = if let Some(max_age) = cookie.max_age() {
+            Some(now + max_age.num_seconds())
+        } else {
+            cookie.expires().map(|tm| tm.to_timespec().sec)
+        };
+
+        let stored = StoredCookie {
+            name: cookie.name().to_owned(),
+            value: cookie.value().to_owned(),
+            secure: cookie.secure(),
+            domain,
+            host_only,
+            path,
+            expires,
+        };
+
+        let mut cookies = self.inner.borrow_mut();
+        cookies.retain(|c| {
+            !(c.name == stored.name && c.domain == stored.domain && c.path == stored.path)
+        });
+        if !stored.is_expired(now) {
+            cookies.push(stored);
+        }
+    }
+
+    /// Store cookies from `Set-Cookie` headers of the response to the `uri`.
+    ///
+    /// Malformed cookies are ignored.
+    pub fn store(&self, uri: &Uri, headers: &HeaderMap) {
+        for val in headers.get_all(header::SET_COOKIE).iter() {
+            if let Ok(s) = str::from_utf8(val.as_bytes()) {
+                if let Ok(cookie) = Cookie::parse_encoded(s) {
+                    self.insert(uri, &cookie);
+                }
+            }
+        }
+    }
+
+    /// Cookies that should be sent with a request to the `uri`.
+    ///
+    /// Cookies with longer paths are listed first.
+    pub fn matches(&self, uri: &Uri) -> Vec<Cookie<'static>> {
+        let host = match uri.host() {
+            Some(host) => host.to_lowercase(),
+            None => return Vec::new(),
+        };
+        let secure = is_secure(uri);
+        let now = time::get_time().sec;
+
+        let mut cookies = self.inner.borrow_mut();
+        cookies.retain(|c| !c.is_expired(now));
+
+        let mut matched: Vec<&StoredCookie> = cookies
+            .iter()
+            .filter(|c| c.matches(&host, uri.path(), secure))
+            .collect();
+        matched.sort_by(|a, b| b.path.len().cmp(&a.path.len()));
+        matched
+            .into_iter()
+            .map(|c| Cookie::new(c.name.clone(), c.value.clone()))
+            .collect()
+    }
+
+    /// Add matching cookies to the `Cookie` header
+    pub(crate) fn apply(&self, uri: &Uri, headers: &mut HeaderMap) {
+        let mut cookie = match headers.get(header::COOKIE) {
+            Some(val) => String::from_utf8_lossy(val.as_bytes()).into_owned(),
+            None => String::new(),
+        };
+        let len = cookie.len();
+
+        for c in self.matches(uri) {
+            if !cookie.is_empty() {
+                cookie.push_str("; ");
+            }
+            let name = percent_encode(c.name().as_bytes(), USERINFO_ENCODE_SET);
+            let value = percent_encode(c.value().as_bytes(), USERINFO_ENCODE_SET);
+            cookie.push_str(&format!("{}={}", name, value));
+        }
+
+        if cookie.len() != len {
+            if let Ok(val) = HeaderValue::from_str(&cookie) {
+                headers.insert(header::COOKIE, val);
+            }
+        }
+    }
+
+    /// Write persistent cookies in Netscape `cookies.txt` format.
+    ///
+    /// Session cookies are not written.
+    pub fn save<W: Write>(&self, mut w: W) -> io::Result<()> {
+        writeln!(w, "# Netscape HTTP Cookie File")?;
+        for c in self.inner.borrow().iter() {
+            if let Some(expires) = c.expires {
+                writeln!(
+                    w,
+                    "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                    c.domain,
+                    if c.host_only { "FALSE" } else { "TRUE" },
+                    c.path,
+                    if c.secure { "TRUE" } else { "FALSE" },
+                    expires,
+                    percent_encode(c.name.as_bytes(), USERINFO_ENCODE_SET),
+                    percent_encode(c.value.as_bytes(), USERINFO_ENCODE_SET)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Load cookies written by `CookieJar::save()`.
+    ///
+    /// Expired cookies are skipped.
+    pub fn load<R: BufRead>(r: R) -> io::Result<CookieJar> {
+        let jar = CookieJar::new();
+        let now = time::get_time().sec;
+        {
+            let mut cookies = jar.inner.borrow_mut();
+            for line in r.lines() {
+                let line = line?;
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let stored = match parse_line(&line) {
+                    Some(stored) => stored,
+                    None => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("Malformed cookie line: {}", line),
+                        ))
+                    }
+                };
+                if !stored.is_expired(now) {
+                    cookies.push(stored);
+                }
+            }
+        }
+        Ok(jar)
+    }
+}
+
+fn parse_line(line: &str) -> Option<StoredCookie> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() != 7 {
+        return None;
+    }
+    let expires = match fields[4].parse() {
+        Ok(expires) => expires,
+        Err(_) => return None,
+    };
+    Some(StoredCookie {
+        domain: fields[0].to_lowercase(),
+        host_only: fields[1] != "TRUE",
+        path: fields[2].to_owned(),
+        secure: fields[3] == "TRUE",
+        expires: Some(expires),
+        name: percent_decode(fields[5].as_bytes())
+            .decode_utf8_lossy()
+            .into_owned(),
+        value: percent_decode(fields[6].as_bytes())
+            .decode_utf8_lossy()
+            .into_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    fn uri(s: &str) -> Uri {
+        s.parse().unwrap()
+    }
+
+    fn names(jar: &CookieJar, s: &str) -> Vec<String> {
+        jar.matches(&uri(s))
+            .iter()
+            .map(|c| c.name().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_domain_and_path() {
+        let jar = CookieJar::new();
+        let src = uri("http://www.example.com/docs/index.html");
+        jar.insert(&src, &Cookie::parse("host=1").unwrap());
+        jar.insert(&src, &Cookie::parse("domain=1; Domain=.example.com").unwrap());
+        jar.insert(&src, &Cookie::parse("root=1; Path=/").unwrap());
+        jar.insert(&src, &Cookie::parse("other=1; Domain=rust-lang.org").unwrap());
+
+        assert_eq!(jar.len(), 3);
+        assert_eq!(
+            names(&jar, "http://www.example.com/docs/a"),
+            vec!["host", "domain", "root"]
+        );
+        assert_eq!(names(&jar, "http://www.example.com/"), vec!["root"]);
+        assert_eq!(names(&jar, "http://api.example.com/docs"), vec!["domain"]);
+        assert_eq!(names(&jar, "http://www.example.com/docsx"), Vec::<String>::new());
+        assert_eq!(names(&jar, "http://example.org/docs"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_domain_restrictions() {
+        let jar = CookieJar::new();
+        let src = uri("http://www.example.com/");
+        jar.insert(&src, &Cookie::parse("tld=1; Domain=com").unwrap());
+        jar.insert(&src, &Cookie::parse("dot=1; Domain=.com").unwrap());
+        assert!(jar.is_empty());
+
+        let src = uri("http://10.0.0.1/");
+        jar.insert(&src, &Cookie::parse("suffix=1; Domain=0.0.1").unwrap());
+        jar.insert(&src, &Cookie::parse("ip=1; Domain=10.0.0.1").unwrap());
+        assert_eq!(names(&jar, "http://10.0.0.1/"), vec!["ip"]);
+        assert_eq!(names(&jar, "http://110.0.0.1/"), Vec::<String>::new());
+
+        let src = uri("http://localhost/");
+        jar.insert(&src, &Cookie::parse("local=1; Domain=localhost").unwrap());
+        assert_eq!(names(&jar, "http://localhost/"), vec!["local"]);
+        assert_eq!(names(&jar, "http://a.localhost/"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_secure_and_expiry() {
+        let jar = CookieJar::new();
+        let src = uri("https://example.com/");
+        jar.insert(&src, &Cookie::parse("secure=1; Secure").unwrap());
+        jar.insert(&src, &Cookie::parse("session=1").unwrap());
+        jar.insert(&src, &Cookie::parse("expired=1; Max-Age=0").unwrap());
+        assert_eq!(names(&jar, "http://example.com/"), vec!["session"]);
+        assert_eq!(names(&jar, "https://example.com/"), vec!["secure", "session"]);
+
+        // replace and remove
+        jar.insert(&src, &Cookie::parse("session=2").unwrap());
+        assert_eq!(jar.matches(&src)[1].value(), "2");
+        jar.insert(
+            &src,
+            &Cookie::parse("session=; Expires=Thu, 01 Jan 1970 00:00:00 GMT").unwrap(),
+        );
+        assert_eq!(names(&jar, "https://example.com/"), vec!["secure"]);
+    }
+
+    #[test]
+    fn test_apply() {
+        let jar = CookieJar::new();
+        let src = uri("http://example.com/");
+        jar.insert(&src, &Cookie::parse("a=1").unwrap());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, HeaderValue::from_static("b=2"));
+        jar.apply(&src, &mut headers);
+        assert_eq!(headers.get(header::COOKIE).unwrap(), "b=2; a=1");
+    }
+
+    #[test]
+    fn test_save_load() {
+        let jar = CookieJar::new();
+        let src = uri("https://example.com/");
+        jar.insert(&src, &Cookie::parse("a=1; Max-Age=3600; Secure").unwrap());
+        jar.insert(&src, &Cookie::parse("b=1; Domain=example.com; Path=/p").unwrap());
+        jar.insert(&src, &Cookie::parse("session=1").unwrap());
+
+        let mut buf = Vec::new();
+        jar.save(&mut buf).unwrap();
+        let loaded = CookieJar::load(BufReader::new(&buf[..])).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(*loaded.inner.borrow(), vec![jar.inner.borrow()[0].clone()]);
+
+        assert!(CookieJar::load(BufReader::new(&b"broken line"[..])).is_err());
+    }
+}
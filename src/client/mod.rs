@@ -27,18 +27,30 @@
 //! }
 //! ```
 mod connector;
+mod cookies;
+mod multipart;
 mod parser;
 mod pipeline;
+mod resolver;
 mod request;
 mod response;
+mod retry;
 mod writer;
 
-pub use self::connector::{ClientConnector, ClientConnectorError, ClientConnectorStats,
-                          Connect, Connection, Pause, Resume};
+pub use self::connector::{ActiveConnection, ClientConnector, ClientConnectorError,
+                          ClientConnectorStats, Connect, Connection, HostSnapshot,
+                          IdleConnection, Pause, PoolSnapshot, PoolStatus, Resume,
+                          WaitHistogram};
+pub use self::cookies::CookieJar;
+pub use self::multipart::{MultipartBody, Part};
 pub(crate) use self::parser::{HttpResponseParser, HttpResponseParserError};
 pub use self::pipeline::{SendRequest, SendRequestError};
 pub use self::request::{ClientRequest, ClientRequestBuilder};
+pub use self::resolver::{
+    LookupFuture, ResolveFuture, Resolver, StaticResolver, SystemResolver,
+};
 pub use self::response::ClientResponse;
+pub use self::retry::{RetryPolicy, RetryPolicyError};
 pub(crate) use self::writer::HttpClientWriter;
 
 use error::ResponseError;
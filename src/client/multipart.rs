@@ -0,0 +1,349 @@
+//! Multipart `form-data` request body
+use std::fmt::Write as FmtWrite;
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{stream, Stream};
+use http::header::{HeaderMap, HeaderName, HeaderValue};
+use mime::Mime;
+use mime_guess::guess_mime_type;
+use rand::{self, Rng};
+
+use body::{Binary, Body, BodyStream};
+use error::Error;
+use fs::ChunkedReadFile;
+
+/// A `multipart/form-data` request body builder
+///
+/// Parts with in-memory content produce body with known size,
+/// if any part is a stream of unknown size, body is sent with
+/// chunked transfer encoding.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::client::{self, MultipartBody, Part};
+///
+/// fn main() {
+///     let mut form = MultipartBody::new();
+///     form.text("title", "Report")
+///         .part(Part::bytes("file", &b"data"[..]).file_name("report.txt"));
+///
+///     let req = client::post("http://www.rust-lang.org")
+///         .multipart(form)
+///         .unwrap();
+/// }
+/// ```
+pub struct MultipartBody {
+    boundary: String,
+    parts: Vec<Part>,
+}
+
+/// A single part of multipart body
+pub struct Part {
+    name: String,
+    file_name: Option<String>,
+    content_type: Option<Mime>,
+    headers: HeaderMap,
+    body: PartBody,
+}
+
+enum PartBody {
+    Binary(Binary),
+    Stream(BodyStream, Option<u64>),
+}
+
+impl Part {
+    /// Create text part
+    pub fn text<N: Into<String>, V: Into<String>>(name: N, value: V) -> Part {
+        Part::new(name.into(), PartBody::Binary(Binary::from(value.into())))
+    }
+
+    /// Create part with in-memory content
+    pub fn bytes<N: Into<String>, B: Into<Binary>>(name: N, data: B) -> Part {
+        Part::new(name.into(), PartBody::Binary(data.into()))
+    }
+
+    /// Create part with streaming content of unknown size.
+    ///
+    /// Size could be specified with `Part::length()` method.
+    pub fn stream<N, S, E>(name: N, stream: S) -> Part
+    where
+        N: Into<String>,
+        S: Stream<Item = Bytes, Error = E> + 'static,
+        E: Into<Error>,
+    {
+        Part::new(
+            name.into(),
+            PartBody::Stream(Box::new(stream.map_err(|e| e.into())), None),
+        )
+    }
+
+    /// Create file part, file is streamed from disk.
+    ///
+    /// File name and content type are derived from the path.
+    pub fn file<N: Into<String>, P: AsRef<Path>>(name: N, path: P) -> io::Result<Part> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        let size = file.metadata()?.len();
+
+        let mut part = Part::new(
+            name.into(),
+            PartBody::Stream(Box::new(ChunkedReadFile::new(file, size)), Some(size)),
+        );
+        part.content_type = Some(guess_mime_type(path));
+        part.file_name = path.file_name()
+            .map(|name| name.to_string_lossy().into_owned());
+        Ok(part)
+    }
+
+    fn new(name: String, body: PartBody) -> Part {
+        Part {
+            name,
+            body,
+            file_name: None,
+            content_type: None,
+            headers: HeaderMap::new(),
+        }
+    }
+
+    /// Set `filename` parameter of the `Content-Disposition` header
+    pub fn file_name<T: Into<String>>(mut self, name: T) -> Part {
+        self.file_name = Some(name.into());
+        self
+    }
+
+    /// Set part's content type.
+    ///
+    /// By default `application/octet-stream` is used for file parts.
+    pub fn content_type(mut self, mime: Mime) -> Part {
+        self.content_type = Some(mime);
+        self
+    }
+
+    /// Add part header
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Part {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Set size of streaming content
+    pub fn length(mut self, len: u64) -> Part {
+        if let PartBody::Stream(_, ref mut size) = self.body {
+            *size = Some(len);
+        }
+        self
+    }
+
+    fn len(&self) -> Option<u64> {
+        match self.body {
+            PartBody::Binary(ref bin) => Some(bin.len() as u64),
+            PartBody::Stream(_, size) => size,
+        }
+    }
+
+    fn write_headers(&self, boundary: &str, buf: &mut BytesMut) {
+        let mut head = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"",
+            boundary,
+            escape(&self.name)
+        );
+        if let Some(ref file_name) = self.file_name {
+            let _ = write!(head, "; filename=\"{}\"", escape(file_name));
+        }
+        head.push_str("\r\n");
+
+        match self.content_type {
+            Some(ref mime) => {
+                let _ = write!(head, "Content-Type: {}\r\n", mime);
+            }
+            None if self.file_name.is_some() => {
+                head.push_str("Content-Type: application/octet-stream\r\n");
+            }
+            None => (),
+        }
+        buf.extend_from_slice(head.as_bytes());
+        for (key, value) in &self.headers {
+            buf.reserve(key.as_str().len() + value.len() + 4);
+            buf.put_slice(key.as_str().as_bytes());
+            buf.put_slice(b": ");
+            buf.put_slice(value.as_bytes());
+            buf.put_slice(b"\r\n");
+        }
+        buf.extend_from_slice(b"\r\n");
+    }
+}
+
+/// Quote field and file names, `"` and line breaks are percent encoded
+/// the same way as browsers do
+fn escape(s: &str) -> String {
+    s.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+impl MultipartBody {
+    /// Create new multipart body with random boundary
+    pub fn new() -> MultipartBody {
+        let boundary: String = rand::thread_rng()
+            .gen_ascii_chars()
+            .take(32)
+            .collect();
+        MultipartBody::with_boundary(boundary)
+    }
+
+    /// Create new multipart body with specified boundary
+    pub fn with_boundary<B: Into<String>>(boundary: B) -> MultipartBody {
+        MultipartBody {
+            boundary: boundary.into(),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Multipart boundary
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Add text field
+    pub fn text<N: Into<String>, V: Into<String>>(
+        &mut self, name: N, value: V
+    ) -> &mut Self {
+        self.part(Part::text(name, value))
+    }
+
+    /// Add part
+    pub fn part(&mut self, part: Part) -> &mut Self {
+        self.parts.push(part);
+        self
+    }
+
+    /// Value of the `Content-Type` header for this body
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Size of the encoded body, if size of all parts is known
+    pub fn content_length(&self) -> Option<u64> {
+        let mut len = self.boundary.len() as u64 + 6;
+        for part in &self.parts {
+            let mut buf = BytesMut::new();
+            part.write_headers(&self.boundary, &mut buf);
+            match part.len() {
+                Some(size) => len += buf.len() as u64 + size + 2,
+                None => return None,
+            }
+        }
+        Some(len)
+    }
+
+    /// Convert to a request body.
+    ///
+    /// If all parts are in memory, body is `Body::Binary`, otherwise
+    /// `Body::Streaming`.
+    pub fn into_body(self) -> Body {
+        let MultipartBody { boundary, parts } = self;
+        let in_memory = parts.iter().all(|part| match part.body {
+            PartBody::Binary(_) => true,
+            PartBody::Stream(..) => false,
+        });
+
+        if in_memory {
+            let mut buf = BytesMut::new();
+            for part in parts {
+                part.write_headers(&boundary, &mut buf);
+                if let PartBody::Binary(ref bin) = part.body {
+                    buf.extend_from_slice(bin.as_ref());
+                }
+                buf.extend_from_slice(b"\r\n");
+            }
+            buf.extend_from_slice(format!("--{}--\r\n", boundary).as_bytes());
+            Body::Binary(buf.into())
+        } else {
+            let mut streams: Vec<BodyStream> = Vec::with_capacity(parts.len() * 2 + 1);
+            for part in parts {
+                let mut buf = BytesMut::new();
+                part.write_headers(&boundary, &mut buf);
+                streams.push(chunk(buf.freeze()));
+                streams.push(match part.body {
+                    PartBody::Binary(bin) => chunk(bin.into()),
+                    PartBody::Stream(s, _) => s,
+                });
+                streams.push(chunk(Bytes::from_static(b"\r\n")));
+            }
+            streams.push(chunk(Bytes::from(format!("--{}--\r\n", boundary))));
+            Body::Streaming(Box::new(
+                stream::iter_ok::<_, Error>(streams).flatten(),
+            ))
+        }
+    }
+}
+
+fn chunk(data: Bytes) -> BodyStream {
+    Box::new(stream::once::<_, Error>(Ok(data)))
+}
+
+impl Default for MultipartBody {
+    fn default() -> MultipartBody {
+        MultipartBody::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::Future;
+    use mime;
+
+    const EXPECTED: &[u8] = b"--abbc761f78ff4d7cb7573b5a23f96ef0\r\n\
+        Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+        test\r\n\
+        --abbc761f78ff4d7cb7573b5a23f96ef0\r\n\
+        Content-Disposition: form-data; name=\"file\"; filename=\"a%22.txt\"\r\n\
+        Content-Type: text/plain\r\n\
+        X-Test: 1\r\n\r\n\
+        data\r\n\
+        --abbc761f78ff4d7cb7573b5a23f96ef0--\r\n";
+
+    fn form(file: Part) -> MultipartBody {
+        let mut form = MultipartBody::with_boundary("abbc761f78ff4d7cb7573b5a23f96ef0");
+        form.text("title", "test").part(
+            file.file_name("a\".txt")
+                .content_type(mime::TEXT_PLAIN)
+                .header(
+                    HeaderName::from_static("x-test"),
+                    HeaderValue::from_static("1"),
+                ),
+        );
+        form
+    }
+
+    #[test]
+    fn test_binary_body() {
+        let body = form(Part::bytes("file", &b"data"[..]));
+        assert_eq!(body.content_length(), Some(EXPECTED.len() as u64));
+
+        match body.into_body() {
+            Body::Binary(bin) => assert_eq!(bin.as_ref(), EXPECTED),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_streaming_body() {
+        let body = form(Part::stream("file", chunk(Bytes::from_static(b"data"))));
+        assert_eq!(body.content_length(), None);
+
+        let body =
+            form(Part::stream("file", chunk(Bytes::from_static(b"data"))).length(4));
+        assert_eq!(body.content_length(), Some(EXPECTED.len() as u64));
+
+        match body.into_body() {
+            Body::Streaming(s) => {
+                assert_eq!(s.concat2().wait().unwrap(), Bytes::from_static(EXPECTED))
+            }
+            _ => panic!(),
+        }
+    }
+}
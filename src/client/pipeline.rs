@@ -8,6 +8,8 @@ use tokio_core::reactor::Timeout;
 
 use actix::prelude::*;
 
+use super::retry::RetryPolicy;
+use super::CookieJar;
 use super::HttpClientWriter;
 use super::{ClientConnector, ClientConnectorError, Connect, Connection};
 use super::{ClientRequest, ClientResponse};
@@ -67,11 +69,16 @@ enum State {
 #[must_use = "SendRequest does nothing unless polled"]
 pub struct SendRequest {
     req: ClientRequest,
+    cookies: Option<CookieJar>,
     state: State,
     conn: Addr<Unsync, ClientConnector>,
     conn_timeout: Duration,
     wait_timeout: Duration,
     timeout: Option<Timeout>,
+    timeout_dur: Option<Duration>,
+    retry: Option<(RetryPolicy, ClientRequest)>,
+    attempt: u32,
+    delay: Option<Timeout>,
 }
 
 impl SendRequest {
@@ -80,13 +87,20 @@ impl SendRequest {
     }
 
     pub(crate) fn with_connector(
-        req: ClientRequest, conn: Addr<Unsync, ClientConnector>
+        mut req: ClientRequest, conn: Addr<Unsync, ClientConnector>
     ) -> SendRequest {
+        let retry = req.take_retry_policy()
+            .map(|policy| (policy, req.replay()));
         SendRequest {
             req,
             conn,
+            retry,
+            cookies: None,
             state: State::New,
             timeout: None,
+            timeout_dur: None,
+            attempt: 1,
+            delay: None,
             wait_timeout: Duration::from_secs(5),
             conn_timeout: Duration::from_secs(1),
         }
@@ -95,9 +109,14 @@ impl SendRequest {
     pub(crate) fn with_connection(req: ClientRequest, conn: Connection) -> SendRequest {
         SendRequest {
             req,
+            cookies: None,
             state: State::Connection(conn),
             conn: ClientConnector::from_registry(),
             timeout: None,
+            timeout_dur: None,
+            retry: None,
+            attempt: 1,
+            delay: None,
             wait_timeout: Duration::from_secs(5),
             conn_timeout: Duration::from_secs(1),
         }
@@ -109,6 +128,7 @@ impl SendRequest {
     /// Default value is 5 seconds.
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(Timeout::new(timeout, Arbiter::handle()).unwrap());
+        self.timeout_dur = Some(timeout);
         self
     }
 
@@ -137,6 +157,71 @@ impl Future for SendRequest {
     type Error = SendRequestError;
 
     fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        loop {
+            if let Some(mut delay) = self.delay.take() {
+                match delay.poll() {
+                    Ok(Async::NotReady) => {
+                        self.delay = Some(delay);
+                        return Ok(Async::NotReady);
+                    }
+                    _ => self.next_attempt(),
+                }
+            }
+
+            let delay = match self.poll_attempt() {
+                Ok(Async::NotReady) => return Ok(Async::NotReady),
+                Ok(Async::Ready(resp)) => {
+                    let delay = match self.retry {
+                        Some((ref policy, _)) => policy.on_response(
+                            self.attempt,
+                            self.req.method(),
+                            resp.status(),
+                            resp.headers(),
+                        ),
+                        None => None,
+                    };
+                    match delay {
+                        Some(delay) => delay,
+                        None => return Ok(Async::Ready(resp)),
+                    }
+                }
+                Err(err) => {
+                    let delay = match self.retry {
+                        Some((ref policy, _)) => {
+                            policy.on_error(self.attempt, self.req.method(), &err)
+                        }
+                        None => None,
+                    };
+                    match delay {
+                        Some(delay) => delay,
+                        None => return Err(err),
+                    }
+                }
+            };
+            debug!(
+                "Retry request to {} in {:?}, attempt {} failed",
+                self.req.uri(),
+                delay,
+                self.attempt
+            );
+            self.delay = Some(Timeout::new(delay, Arbiter::handle()).unwrap());
+        }
+    }
+}
+
+impl SendRequest {
+    fn next_attempt(&mut self) {
+        if let Some((_, ref req)) = self.retry {
+            self.req = req.replay();
+        }
+        self.attempt += 1;
+        self.cookies = None;
+        self.state = State::New;
+        self.timeout = self.timeout_dur
+            .map(|dur| Timeout::new(dur, Arbiter::handle()).unwrap());
+    }
+
+    fn poll_attempt(&mut self) -> Poll<ClientResponse, SendRequestError> {
         loop {
             let state = mem::replace(&mut self.state, State::None);
 
@@ -164,12 +249,22 @@ impl Future for SendRequest {
                     }
                 },
                 State::Connection(conn) => {
+                    self.cookies = self.req
+                        .cookie_jar()
+                        .or_else(|| conn.cookies())
+                        .cloned();
+                    if let Some(ref jar) = self.cookies {
+                        let uri = self.req.uri().clone();
+                        jar.apply(&uri, self.req.headers_mut());
+                    }
+
                     let mut writer = HttpClientWriter::new(SharedBytes::default());
                     writer.start(&mut self.req)?;
 
                     let body = match self.req.replace_body(Body::Empty) {
                         Body::Streaming(stream) => IoBody::Payload(stream),
                         Body::Actor(ctx) => IoBody::Actor(ctx),
+                        Body::File(file) => IoBody::Payload(Box::new(file)),
                         _ => IoBody::Done,
                     };
 
@@ -199,6 +294,9 @@ impl Future for SendRequest {
 
                     match pl.parse() {
                         Ok(Async::Ready(mut resp)) => {
+                            if let Some(ref jar) = self.cookies {
+                                jar.store(self.req.uri(), resp.headers());
+                            }
                             resp.set_pipeline(pl);
                             return Ok(Async::Ready(resp));
                         }
@@ -10,10 +10,14 @@ use futures::Stream;
 use percent_encoding::{percent_encode, USERINFO_ENCODE_SET};
 use serde::Serialize;
 use serde_json;
+use serde_urlencoded;
 use url::Url;
 
 use super::connector::{ClientConnector, Connection};
+use super::cookies::CookieJar as SharedCookieJar;
+use super::multipart::MultipartBody;
 use super::pipeline::SendRequest;
+use super::retry::{RetryPolicy, RetryPolicyError};
 use body::Body;
 use error::Error;
 use header::{ContentEncoding, Header, IntoHeaderValue};
@@ -63,6 +67,8 @@ pub struct ClientRequest {
     response_decompress: bool,
     buffer_capacity: usize,
     conn: ConnectionType,
+    cookie_jar: Option<SharedCookieJar>,
+    retry: Option<RetryPolicy>,
 }
 
 enum ConnectionType {
@@ -86,6 +92,8 @@ impl Default for ClientRequest {
             response_decompress: true,
             buffer_capacity: 32_768,
             conn: ConnectionType::Default,
+            cookie_jar: None,
+            retry: None,
         }
     }
 }
@@ -220,6 +228,52 @@ impl ClientRequest {
         self.buffer_capacity
     }
 
+    /// Cookie jar of the request
+    #[inline]
+    pub fn cookie_jar(&self) -> Option<&SharedCookieJar> {
+        self.cookie_jar.as_ref()
+    }
+
+    /// Retry policy of the request
+    #[inline]
+    pub fn retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry.as_ref()
+    }
+
+    pub(crate) fn take_retry_policy(&mut self) -> Option<RetryPolicy> {
+        self.retry.take()
+    }
+
+    /// Copy of the request for the next attempt.
+    ///
+    /// Streaming bodies and explicit connections are not copied.
+    pub(crate) fn replay(&self) -> ClientRequest {
+        ClientRequest {
+            uri: self.uri.clone(),
+            method: self.method.clone(),
+            version: self.version,
+            headers: self.headers.clone(),
+            body: match self.body {
+                Body::Binary(ref bin) => Body::Binary(bin.clone()),
+                _ => Body::Empty,
+            },
+            chunked: self.chunked,
+            upgrade: self.upgrade,
+            timeout: self.timeout,
+            encoding: self.encoding,
+            response_decompress: self.response_decompress,
+            buffer_capacity: self.buffer_capacity,
+            conn: match self.conn {
+                ConnectionType::Connector(ref conn) => {
+                    ConnectionType::Connector(conn.clone())
+                }
+                _ => ConnectionType::Default,
+            },
+            cookie_jar: self.cookie_jar.clone(),
+            retry: None,
+        }
+    }
+
     /// Get body of this response
     #[inline]
     pub fn body(&self) -> &Body {
@@ -306,6 +360,41 @@ impl ClientRequestBuilder {
         self
     }
 
+    /// Append serialized query parameters to the request's URI.
+    ///
+    /// Parameters are merged with a query string that is already present
+    /// in the URI.
+    ///
+    /// ```rust
+    /// # extern crate actix_web;
+    /// use actix_web::client::ClientRequest;
+    ///
+    /// fn main() {
+    ///     let req = ClientRequest::build()
+    ///         .uri("http://www.rust-lang.org/search?page=1")
+    ///         .query(&[("q", "actix web")]).unwrap()
+    ///         .finish().unwrap();
+    ///     assert_eq!(req.uri().query(), Some("page=1&q=actix+web"));
+    /// }
+    /// ```
+    pub fn query<T: Serialize>(&mut self, query: &T) -> Result<&mut Self, Error> {
+        let params = serde_urlencoded::to_string(query)?;
+        if params.is_empty() {
+            return Ok(self);
+        }
+        if let Some(parts) = parts(&mut self.request, &self.err) {
+            let mut uri = parts.uri.to_string();
+            match parts.uri.query() {
+                Some(q) if !q.is_empty() => uri.push('&'),
+                Some(_) => (),
+                None => uri.push('?'),
+            }
+            uri.push_str(&params);
+            parts.uri = Uri::try_from(uri.as_str()).map_err(HttpError::from)?;
+        }
+        Ok(self)
+    }
+
     /// Set HTTP method of this request.
     #[inline]
     pub fn method(&mut self, method: Method) -> &mut Self {
@@ -507,6 +596,18 @@ impl ClientRequestBuilder {
         self
     }
 
+    /// Use cookie jar for this request.
+    ///
+    /// Matching cookies from the jar are sent with the request,
+    /// cookies set by the response are stored in the jar. Request's jar
+    /// takes precedence over connector's jar.
+    pub fn cookie_jar(&mut self, jar: &SharedCookieJar) -> &mut Self {
+        if let Some(parts) = parts(&mut self.request, &self.err) {
+            parts.cookie_jar = Some(jar.clone());
+        }
+        self
+    }
+
     /// Do not add default request headers.
     /// By default `Accept-Encoding` header is set.
     pub fn no_default_headers(&mut self) -> &mut Self {
@@ -551,6 +652,17 @@ impl ClientRequestBuilder {
         self
     }
 
+    /// Retry failed requests according to the policy.
+    ///
+    /// Request body must be replayable, streaming body or explicit
+    /// connection can not be used with retry policy.
+    pub fn retry(&mut self, policy: RetryPolicy) -> &mut Self {
+        if let Some(parts) = parts(&mut self.request, &self.err) {
+            parts.retry = Some(policy);
+        }
+        self
+    }
+
     /// Send request using existing `Connection`
     pub fn with_connection(&mut self, conn: Connection) -> &mut Self {
         if let Some(parts) = parts(&mut self.request, &self.err) {
@@ -628,6 +740,15 @@ impl ClientRequestBuilder {
             );
         }
         request.body = body.into();
+
+        if request.retry.is_some() {
+            if request.body.is_streaming() {
+                return Err(RetryPolicyError::StreamingBody.into());
+            }
+            if let ConnectionType::Connection(_) = request.conn {
+                return Err(RetryPolicyError::Connection.into());
+            }
+        }
         Ok(request)
     }
 
@@ -649,6 +770,58 @@ impl ClientRequestBuilder {
         self.body(body)
     }
 
+    /// Set a urlencoded body and generate `ClientRequest`
+    ///
+    /// `Content-Type` header is set to `application/x-www-form-urlencoded`
+    /// unless it is already set.
+    ///
+    /// `ClientRequestBuilder` can not be used after this call.
+    pub fn form<T: Serialize>(&mut self, value: T) -> Result<ClientRequest, Error> {
+        let body = serde_urlencoded::to_string(&value)?;
+
+        let contains = if let Some(parts) = parts(&mut self.request, &self.err) {
+            parts.headers.contains_key(header::CONTENT_TYPE)
+        } else {
+            true
+        };
+        if !contains {
+            self.header(
+                header::CONTENT_TYPE,
+                "application/x-www-form-urlencoded",
+            );
+        }
+
+        self.body(body)
+    }
+
+    /// Set a `multipart/form-data` body and generate `ClientRequest`
+    ///
+    /// `Content-Length` header is set if size of all parts is known,
+    /// otherwise body is streamed.
+    ///
+    /// `ClientRequestBuilder` can not be used after this call.
+    pub fn multipart(&mut self, form: MultipartBody) -> Result<ClientRequest, Error> {
+        let contains = if let Some(parts) = parts(&mut self.request, &self.err) {
+            parts.headers.contains_key(header::CONTENT_TYPE)
+        } else {
+            true
+        };
+        if !contains {
+            self.header(header::CONTENT_TYPE, form.content_type());
+        }
+
+        let len = form.content_length();
+        match form.into_body() {
+            Body::Streaming(stream) => {
+                if let Some(len) = len {
+                    self.header(header::CONTENT_LENGTH, len.to_string());
+                }
+                self.body(Body::Streaming(stream))
+            }
+            body => self.body(body),
+        }
+    }
+
     /// Set a streaming body and generate `ClientRequest`.
     ///
     /// `ClientRequestBuilder` can not be used after this call.
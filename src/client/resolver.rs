@@ -0,0 +1,433 @@
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, SocketAddr};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use actix::actors::ConnectorError;
+use actix::Arbiter;
+use futures::future::{self, Either};
+use futures::{Async, Future, Poll};
+use tokio_core::net::{TcpStream, TcpStreamNew};
+use tokio_core::reactor::{Handle, Timeout};
+use trust_dns_resolver::config::{ResolverConfig, ResolverOpts};
+use trust_dns_resolver::ResolverFuture;
+
+/// Future returned by `Resolver::resolve()`
+pub type ResolveFuture = Box<Future<Item = Vec<IpAddr>, Error = ConnectorError>>;
+
+/// Future returned by `Resolver::lookup()`
+///
+/// Resolves to a list of ip addresses and an instant until which
+/// addresses are valid, if it is known.
+pub type LookupFuture =
+    Box<Future<Item = (Vec<IpAddr>, Option<Instant>), Error = ConnectorError>>;
+
+/// Host name resolver used by `ClientConnector`
+///
+/// ```rust
+/// # extern crate actix;
+/// # extern crate actix_web;
+/// use actix::Actor;
+/// use actix_web::client::{ClientConnector, StaticResolver, SystemResolver};
+///
+/// fn main() {
+///     let sys = actix::System::new("test");
+///
+///     // resolve "backend" to localhost, other hosts with system resolver
+///     let conn = ClientConnector::default()
+///         .resolver(
+///             StaticResolver::new()
+///                 .host("backend", "127.0.0.1".parse().unwrap())
+///                 .fallback(SystemResolver))
+///         .start();
+/// }
+/// ```
+pub trait Resolver: 'static {
+    /// Resolve host name to a list of ip addresses
+    fn resolve(&self, host: &str) -> ResolveFuture;
+
+    /// Resolve host name to a list of ip addresses and the instant
+    /// until which they are valid.
+    ///
+    /// `ClientConnector` uses validity period for expiring cached
+    /// addresses. Default implementation uses `resolve()` and reports
+    /// unknown validity.
+    fn lookup(&self, host: &str) -> LookupFuture {
+        Box::new(self.resolve(host).map(|addrs| (addrs, None)))
+    }
+}
+
+thread_local!(static DNS: RefCell<Option<ResolverFuture>> = RefCell::new(None));
+
+/// Resolver that uses system DNS configuration
+///
+/// This is default resolver of `ClientConnector`. Lookups report
+/// validity of addresses according to TTL of DNS records.
+pub struct SystemResolver;
+
+impl Resolver for SystemResolver {
+    fn resolve(&self, host: &str) -> ResolveFuture {
+        Box::new(self.lookup(host).map(|(addrs, _)| addrs))
+    }
+
+    fn lookup(&self, host: &str) -> LookupFuture {
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Box::new(future::ok((vec![ip], None)));
+        }
+        let fut = DNS.with(|dns| {
+            let mut dns = dns.borrow_mut();
+            if dns.is_none() {
+                *dns = Some(system_resolver());
+            }
+            dns.as_ref().unwrap().lookup_ip(host)
+        });
+        Box::new(fut.then(|res| match res {
+            Ok(ips) => {
+                let valid_until = ips.valid_until();
+                Ok((ips.iter().collect(), Some(valid_until)))
+            }
+            Err(err) => Err(ConnectorError::Resolver(format!("{}", err))),
+        }))
+    }
+}
+
+/// Create resolver for current thread's event loop
+fn system_resolver() -> ResolverFuture {
+    match ResolverFuture::from_system_conf(Arbiter::handle()) {
+        Ok(resolver) => resolver,
+        Err(err) => {
+            warn!("Can not read system dns configuration: {}", err);
+            ResolverFuture::new(
+                ResolverConfig::default(),
+                ResolverOpts::default(),
+                Arbiter::handle(),
+            )
+        }
+    }
+}
+
+/// Resolver with static host map
+///
+/// Hosts that are not in the map are resolved with fallback resolver,
+/// if it is set, otherwise resolution fails.
+#[derive(Default)]
+pub struct StaticResolver {
+    hosts: HashMap<String, Vec<IpAddr>>,
+    fallback: Option<Box<Resolver>>,
+}
+
+impl StaticResolver {
+    /// Create new empty static resolver
+    pub fn new() -> StaticResolver {
+        StaticResolver::default()
+    }
+
+    /// Add address for the host. Host could have several addresses.
+    pub fn host<H: AsRef<str>>(mut self, host: H, addr: IpAddr) -> Self {
+        self.hosts
+            .entry(host.as_ref().to_lowercase())
+            .or_insert_with(Vec::new)
+            .push(addr);
+        self
+    }
+
+    /// Set resolver for hosts that are not in the map
+    pub fn fallback<R: Resolver>(mut self, resolver: R) -> Self {
+        self.fallback = Some(Box::new(resolver));
+        self
+    }
+}
+
+impl Resolver for StaticResolver {
+    fn resolve(&self, host: &str) -> ResolveFuture {
+        Box::new(self.lookup(host).map(|(addrs, _)| addrs))
+    }
+
+    fn lookup(&self, host: &str) -> LookupFuture {
+        if let Some(addrs) = self.hosts.get(&host.to_lowercase()) {
+            return Box::new(future::ok((addrs.clone(), None)));
+        }
+        match self.fallback {
+            Some(ref resolver) => resolver.lookup(host),
+            None => Box::new(future::err(ConnectorError::Resolver(format!(
+                "Host is not found: {}",
+                host
+            )))),
+        }
+    }
+}
+
+/// Resolver with results cache
+///
+/// Entries expire when lookup validity ends, `ttl` limits lifetime
+/// of an entry. Results with unknown validity are cached for `ttl`
+/// period only if it is set.
+pub(crate) struct CachedResolver {
+    resolver: Box<Resolver>,
+    ttl: Option<Duration>,
+    cache: Rc<RefCell<HashMap<String, (Instant, Vec<IpAddr>)>>>,
+}
+
+impl Default for CachedResolver {
+    fn default() -> CachedResolver {
+        CachedResolver::new(SystemResolver)
+    }
+}
+
+impl CachedResolver {
+    pub fn new<R: Resolver>(resolver: R) -> CachedResolver {
+        CachedResolver {
+            resolver: Box::new(resolver),
+            ttl: None,
+            cache: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    pub fn set_resolver<R: Resolver>(&mut self, resolver: R) {
+        self.resolver = Box::new(resolver);
+        self.cache.borrow_mut().clear();
+    }
+
+    pub fn set_ttl(&mut self, ttl: Option<Duration>) {
+        self.ttl = ttl;
+        self.cache.borrow_mut().clear();
+    }
+
+    pub fn resolve(&self, host: &str) -> ResolveFuture {
+        let key = host.to_lowercase();
+        let now = Instant::now();
+        {
+            let mut cache = self.cache.borrow_mut();
+            if let Some(&(expires, ref addrs)) = cache.get(&key) {
+                if expires > now {
+                    return Box::new(future::ok(addrs.clone()));
+                }
+            }
+            cache.remove(&key);
+        }
+
+        let ttl = self.ttl;
+        let cache = Rc::clone(&self.cache);
+        Box::new(self.resolver.lookup(host).map(move |(addrs, valid_until)| {
+            if let Some(expires) = expiration(Instant::now(), valid_until, ttl) {
+                if !addrs.is_empty() {
+                    cache.borrow_mut().insert(key, (expires, addrs.clone()));
+                }
+            }
+            addrs
+        }))
+    }
+
+    /// Resolve host and connect to one of its addresses
+    pub fn connect(
+        &self, host: &str, port: u16, timeout: Duration, attempt_delay: Duration
+    ) -> Box<Future<Item = TcpStream, Error = ConnectorError>> {
+        let handle = Arbiter::handle().clone();
+        let fut = self.resolve(host).and_then(move |addrs| {
+            let addrs = addrs
+                .into_iter()
+                .map(|ip| SocketAddr::new(ip, port))
+                .collect();
+            HappyEyeballs::new(addrs, attempt_delay, handle)
+        });
+
+        let timeout = match Timeout::new(timeout, Arbiter::handle()) {
+            Ok(timeout) => timeout,
+            Err(err) => return Box::new(future::err(ConnectorError::IoError(err))),
+        };
+        Box::new(fut.select2(timeout).then(|res| match res {
+            Ok(Either::A((stream, _))) => Ok(stream),
+            Ok(Either::B(_)) => Err(ConnectorError::Timeout),
+            Err(Either::A((err, _))) => Err(err),
+            Err(Either::B((err, _))) => Err(ConnectorError::IoError(err)),
+        }))
+    }
+}
+
+/// Expiration time of a cache entry, `None` if entry should not be cached
+fn expiration(
+    now: Instant, valid_until: Option<Instant>, ttl: Option<Duration>
+) -> Option<Instant> {
+    let expires = match (valid_until, ttl) {
+        (Some(valid_until), Some(ttl)) => cmp::min(valid_until, now + ttl),
+        (Some(valid_until), None) => valid_until,
+        (None, Some(ttl)) => now + ttl,
+        (None, None) => return None,
+    };
+    if expires > now {
+        Some(expires)
+    } else {
+        None
+    }
+}
+
+/// Order addresses by alternating address families, starting with
+/// the family of the first address (RFC 8305, section 4)
+fn interleave(addrs: Vec<SocketAddr>) -> VecDeque<SocketAddr> {
+    let first_v6 = match addrs.first() {
+        Some(addr) => addr.is_ipv6(),
+        None => return VecDeque::new(),
+    };
+    let (mut first, mut second): (VecDeque<_>, VecDeque<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == first_v6);
+
+    let mut result = VecDeque::with_capacity(first.len() + second.len());
+    loop {
+        match (first.pop_front(), second.pop_front()) {
+            (None, None) => break,
+            (a, b) => {
+                result.extend(a);
+                result.extend(b);
+            }
+        }
+    }
+    result
+}
+
+/// Happy eyeballs connection racing, RFC 8305.
+///
+/// Next address is tried if previous attempt failed or did not
+/// succeed within attempt delay, first established connection wins.
+struct HappyEyeballs {
+    addrs: VecDeque<SocketAddr>,
+    attempts: Vec<TcpStreamNew>,
+    delay: Option<Timeout>,
+    attempt_delay: Duration,
+    handle: Handle,
+    error: Option<ConnectorError>,
+}
+
+impl HappyEyeballs {
+    fn new(addrs: Vec<SocketAddr>, attempt_delay: Duration, handle: Handle) -> Self {
+        HappyEyeballs {
+            addrs: interleave(addrs),
+            attempts: Vec::new(),
+            delay: None,
+            error: None,
+            attempt_delay,
+            handle,
+        }
+    }
+
+    fn delay_elapsed(&mut self) -> bool {
+        match self.delay {
+            Some(ref mut delay) => match delay.poll() {
+                Ok(Async::NotReady) => false,
+                _ => true,
+            },
+            None => false,
+        }
+    }
+}
+
+impl Future for HappyEyeballs {
+    type Item = TcpStream;
+    type Error = ConnectorError;
+
+    fn poll(&mut self) -> Poll<TcpStream, ConnectorError> {
+        loop {
+            let mut idx = 0;
+            while idx < self.attempts.len() {
+                match self.attempts[idx].poll() {
+                    Ok(Async::Ready(stream)) => return Ok(Async::Ready(stream)),
+                    Ok(Async::NotReady) => idx += 1,
+                    Err(err) => {
+                        self.attempts.swap_remove(idx);
+                        self.error = Some(ConnectorError::IoError(err));
+                    }
+                }
+            }
+
+            if self.attempts.is_empty() || self.delay_elapsed() {
+                if let Some(addr) = self.addrs.pop_front() {
+                    self.attempts
+                        .push(TcpStream::connect(&addr, &self.handle));
+                    self.delay = Timeout::new(self.attempt_delay, &self.handle).ok();
+                    continue;
+                }
+                self.delay = None;
+                if self.attempts.is_empty() {
+                    return Err(self.error.take().unwrap_or_else(|| {
+                        ConnectorError::Resolver("Host has no addresses".to_owned())
+                    }));
+                }
+            }
+            return Ok(Async::NotReady);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_resolver() {
+        let resolver = StaticResolver::new()
+            .host("Backend", "127.0.0.1".parse().unwrap())
+            .host("backend", "::1".parse().unwrap());
+        assert_eq!(
+            resolver.resolve("backend").wait().unwrap(),
+            vec![
+                "127.0.0.1".parse::<IpAddr>().unwrap(),
+                "::1".parse().unwrap(),
+            ]
+        );
+        assert!(resolver.resolve("unknown").wait().is_err());
+
+        let resolver = StaticResolver::new()
+            .fallback(StaticResolver::new().host("other", "10.0.0.1".parse().unwrap()));
+        assert_eq!(resolver.resolve("other").wait().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_cached_resolver() {
+        let mut resolver = CachedResolver::new(
+            StaticResolver::new().host("backend", "127.0.0.1".parse().unwrap()),
+        );
+        resolver.set_ttl(Some(Duration::from_secs(60)));
+        assert_eq!(resolver.resolve("backend").wait().unwrap().len(), 1);
+        assert!(resolver.cache.borrow().contains_key("backend"));
+
+        resolver.set_resolver(StaticResolver::new());
+        assert!(resolver.cache.borrow().is_empty());
+        assert!(resolver.resolve("backend").wait().is_err());
+    }
+
+    #[test]
+    fn test_cached_resolver_expiration() {
+        let now = Instant::now();
+        let min = Duration::from_secs(60);
+        let hour = Duration::from_secs(3600);
+
+        assert_eq!(expiration(now, None, None), None);
+        assert_eq!(expiration(now, None, Some(min)), Some(now + min));
+        assert_eq!(expiration(now, Some(now + hour), None), Some(now + hour));
+        assert_eq!(expiration(now, Some(now + hour), Some(min)), Some(now + min));
+        assert_eq!(expiration(now, Some(now + min), Some(hour)), Some(now + min));
+        assert_eq!(expiration(now, Some(now), Some(hour)), None);
+        assert_eq!(expiration(now, None, Some(Duration::from_secs(0))), None);
+
+        // static entries have unknown validity and are not cached without ttl
+        let resolver = CachedResolver::new(
+            StaticResolver::new().host("backend", "127.0.0.1".parse().unwrap()),
+        );
+        assert_eq!(resolver.resolve("backend").wait().unwrap().len(), 1);
+        assert!(resolver.cache.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_interleave() {
+        let addrs: Vec<SocketAddr> = vec![
+            "[::1]:80".parse().unwrap(),
+            "[::2]:80".parse().unwrap(),
+            "[::3]:80".parse().unwrap(),
+            "127.0.0.1:80".parse().unwrap(),
+        ];
+        let result: Vec<_> = interleave(addrs.clone()).into_iter().collect();
+        assert_eq!(result, vec![addrs[0], addrs[3], addrs[1], addrs[2]]);
+    }
+}
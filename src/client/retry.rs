@@ -0,0 +1,350 @@
+use std::cmp;
+use std::time::{Duration, SystemTime};
+
+use actix::actors::ConnectorError;
+use http::header::{self, HeaderMap};
+use http::{Method, StatusCode};
+use rand::{self, Rng};
+
+use super::connector::ClientConnectorError;
+use super::pipeline::SendRequestError;
+use error::ResponseError;
+use header::HttpDate;
+
+/// Retry policy for client requests
+///
+/// Failed attempt is retried after exponentially growing delay,
+/// `base_delay * 2^(attempt - 1)`, limited by `max_delay`. With jitter
+/// enabled, actual delay is a random value between half and full delay.
+///
+/// By default idempotent requests are retried on transient connection
+/// errors (dns resolution errors, connect timeouts and io errors), on
+/// timeouts and on `502`, `503` and `504` responses. Other methods are
+/// retried only if `idempotent_only(false)` is set. Errors that would
+/// repeat on every attempt, like invalid url or ssl errors, are not retried.
+///
+/// Request body must be replayable, streaming bodies can not be used with
+/// retry policy.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use std::time::Duration;
+/// use actix_web::client::{self, RetryPolicy};
+/// use actix_web::http::StatusCode;
+///
+/// fn main() {
+///     let req = client::get("http://www.rust-lang.org")
+///         .retry(RetryPolicy::new(5)
+///                .base_delay(Duration::from_millis(50))
+///                .status(StatusCode::TOO_MANY_REQUESTS))
+///         .finish()
+///         .unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    connect_errors: bool,
+    timeouts: bool,
+    statuses: Vec<StatusCode>,
+    idempotent_only: bool,
+    retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy::new(3)
+    }
+}
+
+impl RetryPolicy {
+    /// Create retry policy with max number of attempts, including first one
+    pub fn new(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: cmp::max(max_attempts, 1),
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+            connect_errors: true,
+            timeouts: true,
+            statuses: vec![
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            idempotent_only: true,
+            retry_after: true,
+        }
+    }
+
+    /// Set delay before the second attempt. By default it is 100 milliseconds.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set max delay between attempts. By default it is 10 seconds.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Enable or disable delay jitter. By default jitter is enabled.
+    pub fn jitter(mut self, enable: bool) -> Self {
+        self.jitter = enable;
+        self
+    }
+
+    /// Retry on transient connection errors. By default it is enabled.
+    pub fn connect_errors(mut self, enable: bool) -> Self {
+        self.connect_errors = enable;
+        self
+    }
+
+    /// Retry on timeouts and io errors. By default it is enabled.
+    pub fn timeouts(mut self, enable: bool) -> Self {
+        self.timeouts = enable;
+        self
+    }
+
+    /// Retry on responses with status code
+    pub fn status(mut self, status: StatusCode) -> Self {
+        if !self.statuses.contains(&status) {
+            self.statuses.push(status);
+        }
+        self
+    }
+
+    /// Do not retry on any response status
+    pub fn no_statuses(mut self) -> Self {
+        self.statuses.clear();
+        self
+    }
+
+    /// Retry requests only if request method is idempotent.
+    /// By default it is enabled.
+    pub fn idempotent_only(mut self, enable: bool) -> Self {
+        self.idempotent_only = enable;
+        self
+    }
+
+    /// Use `Retry-After` response header as delay. By default it is enabled.
+    pub fn retry_after(mut self, enable: bool) -> Self {
+        self.retry_after = enable;
+        self
+    }
+
+    fn method_allowed(&self, method: &Method) -> bool {
+        !self.idempotent_only || match *method {
+            Method::GET
+            | Method::HEAD
+            | Method::PUT
+            | Method::DELETE
+            | Method::OPTIONS
+            | Method::TRACE => true,
+            _ => false,
+        }
+    }
+
+    /// Delay before next attempt, if failed request should be retried
+    pub(crate) fn on_error(
+        &self, attempt: u32, method: &Method, err: &SendRequestError
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let retry = match *err {
+            SendRequestError::Connector(ref err) => {
+                self.connect_errors && is_transient(err)
+            }
+            SendRequestError::Timeout
+            | SendRequestError::Io(_)
+            | SendRequestError::ParseError(_) => self.timeouts,
+        };
+        if retry && self.method_allowed(method) {
+            Some(self.backoff(attempt))
+        } else {
+            None
+        }
+    }
+
+    /// Delay before next attempt, if response should be retried
+    pub(crate) fn on_response(
+        &self, attempt: u32, method: &Method, status: StatusCode, headers: &HeaderMap
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.statuses.contains(&status)
+            || !self.method_allowed(method)
+        {
+            return None;
+        }
+        if self.retry_after {
+            if let Some(delay) = retry_after(headers) {
+                return Some(cmp::min(delay, self.max_delay));
+            }
+        }
+        Some(self.backoff(attempt))
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32 << cmp::min(attempt - 1, 16);
+        let delay = cmp::min(self.base_delay * factor, self.max_delay);
+        if self.jitter {
+            let ms = delay.as_secs() * 1000 + u64::from(delay.subsec_nanos() / 1_000_000);
+            let half = ms / 2;
+            Duration::from_millis(half + rand::thread_rng().gen_range(0, half + 1))
+        } else {
+            delay
+        }
+    }
+}
+
+/// Check if connection error could go away on next attempt
+fn is_transient(err: &ClientConnectorError) -> bool {
+    match *err {
+        ClientConnectorError::Timeout | ClientConnectorError::IoError(_) => true,
+        ClientConnectorError::Connector(ref err) => match *err {
+            ConnectorError::Resolver(_)
+            | ConnectorError::Timeout
+            | ConnectorError::IoError(_) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Parse `Retry-After` header, delay in seconds or http date
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = match headers.get(header::RETRY_AFTER) {
+        Some(value) => match value.to_str() {
+            Ok(value) => value.trim(),
+            Err(_) => return None,
+        },
+        None => return None,
+    };
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    match value.parse::<HttpDate>() {
+        Ok(date) => {
+            let date: SystemTime = date.into();
+            Some(
+                date.duration_since(SystemTime::now())
+                    .unwrap_or_else(|_| Duration::from_secs(0)),
+            )
+        }
+        Err(_) => None,
+    }
+}
+
+/// A set of errors that can occur during retry policy configuration
+#[derive(Fail, Debug)]
+pub enum RetryPolicyError {
+    /// Streaming body can not be replayed
+    #[fail(display = "Retry policy requires replayable request body")]
+    StreamingBody,
+    /// Explicit connection can not be used for more than one attempt
+    #[fail(display = "Retry policy can not be used with explicit connection")]
+    Connection,
+}
+
+/// `InternalServerError` for `RetryPolicyError`
+impl ResponseError for RetryPolicyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::HeaderValue;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new(5)
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(300))
+            .jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(300));
+
+        let policy = policy.jitter(true);
+        for _ in 0..10 {
+            let delay = policy.backoff(2);
+            assert!(delay >= Duration::from_millis(100));
+            assert!(delay <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn test_on_error() {
+        let policy = RetryPolicy::new(2);
+        let err = SendRequestError::Timeout;
+        assert!(policy.on_error(1, &Method::GET, &err).is_some());
+        assert!(policy.on_error(2, &Method::GET, &err).is_none());
+        assert!(policy.on_error(1, &Method::POST, &err).is_none());
+        assert!(
+            policy
+                .idempotent_only(false)
+                .on_error(1, &Method::POST, &err)
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_on_connect_error() {
+        let policy = RetryPolicy::new(2);
+        let err = SendRequestError::Connector(ClientConnectorError::Connector(
+            ConnectorError::Resolver("no records".to_owned()),
+        ));
+        assert!(policy.on_error(1, &Method::GET, &err).is_some());
+        assert!(policy.on_error(1, &Method::POST, &err).is_none());
+        assert!(
+            policy
+                .clone()
+                .idempotent_only(false)
+                .on_error(1, &Method::POST, &err)
+                .is_some()
+        );
+        assert!(
+            policy
+                .clone()
+                .connect_errors(false)
+                .on_error(1, &Method::GET, &err)
+                .is_none()
+        );
+
+        let err = SendRequestError::Connector(ClientConnectorError::InvalidUrl);
+        assert!(policy.on_error(1, &Method::GET, &err).is_none());
+        let err = SendRequestError::Connector(ClientConnectorError::Connector(
+            ConnectorError::InvalidDomainName,
+        ));
+        assert!(policy.on_error(1, &Method::GET, &err).is_none());
+    }
+
+    #[test]
+    fn test_on_response() {
+        let policy = RetryPolicy::new(3).jitter(false);
+        let mut headers = HeaderMap::new();
+        assert!(
+            policy
+                .on_response(1, &Method::GET, StatusCode::NOT_FOUND, &headers)
+                .is_none()
+        );
+        assert_eq!(
+            policy.on_response(1, &Method::GET, StatusCode::SERVICE_UNAVAILABLE, &headers),
+            Some(Duration::from_millis(100))
+        );
+
+        headers.insert(header::RETRY_AFTER, HeaderValue::from_static("2"));
+        assert_eq!(
+            policy.on_response(1, &Method::GET, StatusCode::SERVICE_UNAVAILABLE, &headers),
+            Some(Duration::from_secs(2))
+        );
+        assert!(
+            policy
+                .on_response(1, &Method::POST, StatusCode::SERVICE_UNAVAILABLE, &headers)
+                .is_none()
+        );
+    }
+}
@@ -251,7 +251,7 @@ fn content_encoder(buf: SharedBytes, req: &mut ClientRequest) -> ContentEncoder
             );
             TransferEncoding::eof(buf)
         }
-        Body::Streaming(_) | Body::Actor(_) => {
+        Body::Streaming(_) | Body::Actor(_) | Body::File(_) => {
             if req.upgrade() {
                 if version == Version::HTTP_2 {
                     error!("Connection upgrade is forbidden for HTTP/2");
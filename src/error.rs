@@ -16,10 +16,12 @@ use http_range::HttpRangeParseError;
 use httparse;
 use serde::de::value::Error as DeError;
 use serde_json::error::Error as JsonError;
+use serde_urlencoded::ser::Error as FormError;
 pub use url::ParseError as UrlParseError;
 
 // re-exports
 pub use cookie::ParseError as CookieParseError;
+pub use validate::{FieldError, ValidationErrors};
 
 use handler::Responder;
 use httprequest::HttpRequest;
@@ -116,6 +118,9 @@ impl From<failure::Error> for Error {
 /// `InternalServerError` for `JsonError`
 impl ResponseError for JsonError {}
 
+/// `InternalServerError` for `serde_urlencoded::ser::Error`
+impl ResponseError for FormError {}
+
 /// `InternalServerError` for `UrlParseError`
 impl ResponseError for UrlParseError {}
 
@@ -363,6 +368,107 @@ impl ResponseError for MultipartError {
     }
 }
 
+/// A set of errors that can occur during `MultipartForm` extraction
+#[derive(Fail, Debug)]
+pub enum MultipartFormError {
+    /// Multipart stream error
+    #[fail(display = "{}", _0)]
+    Multipart(#[cause] MultipartError),
+    /// Nested multipart streams are not supported
+    #[fail(display = "Nested multipart streams are not supported")]
+    Nested,
+    /// Field without `name` parameter in `Content-Disposition` header
+    #[fail(display = "Field name is not found")]
+    FieldName,
+    /// Text field is not valid utf-8
+    #[fail(display = "Field {} can not be decoded", _0)]
+    FieldEncoding(String),
+    /// Field is larger than configured limit
+    #[fail(display = "Field {} is too large", _0)]
+    FieldOverflow(String),
+    /// Payload is larger than configured limit
+    #[fail(display = "Multipart payload is too large")]
+    Overflow,
+    /// Form contains more fields than allowed
+    #[fail(display = "Too many fields")]
+    TooManyFields,
+    /// Text fields can not be deserialized
+    #[fail(display = "{}", _0)]
+    Deserialize(#[cause] DeError),
+    /// Temporary file error
+    #[fail(display = "{}", _0)]
+    Io(#[cause] IoError),
+}
+
+impl From<MultipartError> for MultipartFormError {
+    fn from(err: MultipartError) -> MultipartFormError {
+        MultipartFormError::Multipart(err)
+    }
+}
+
+impl From<DeError> for MultipartFormError {
+    fn from(err: DeError) -> MultipartFormError {
+        MultipartFormError::Deserialize(err)
+    }
+}
+
+impl From<IoError> for MultipartFormError {
+    fn from(err: IoError) -> MultipartFormError {
+        MultipartFormError::Io(err)
+    }
+}
+
+/// Return `PayloadTooLarge` for overflow errors, `InternalServerError`
+/// for io errors and `BadRequest` otherwise
+impl ResponseError for MultipartFormError {
+    fn error_response(&self) -> HttpResponse {
+        match *self {
+            MultipartFormError::FieldOverflow(_)
+            | MultipartFormError::Overflow
+            | MultipartFormError::TooManyFields => {
+                HttpResponse::new(StatusCode::PAYLOAD_TOO_LARGE)
+            }
+            MultipartFormError::Io(_) => {
+                HttpResponse::new(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+            _ => HttpResponse::new(StatusCode::BAD_REQUEST),
+        }
+    }
+}
+
+/// A set of errors that can occur during typed header extraction
+#[derive(Fail, Debug)]
+pub enum HeaderError {
+    /// Required header is missing
+    #[fail(display = "Header {} is missing", _0)]
+    Missing(header::HeaderName),
+    /// Header value can not be parsed
+    #[fail(display = "Header {} is malformed", _0)]
+    Malformed(header::HeaderName),
+}
+
+/// Return `BadRequest` with error description for `HeaderError`
+impl ResponseError for HeaderError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::with_body(StatusCode::BAD_REQUEST, format!("{}", self))
+    }
+}
+
+/// A set of errors that can occur during content negotiation
+#[derive(Fail, PartialEq, Debug)]
+pub enum NegotiationError {
+    /// None of the available representations is acceptable
+    #[fail(display = "None of the available representations is acceptable")]
+    NotAcceptable,
+}
+
+/// Return `NotAcceptable` for `NegotiationError`
+impl ResponseError for NegotiationError {
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::with_body(StatusCode::NOT_ACCEPTABLE, format!("{}", self))
+    }
+}
+
 /// Error during handling `Expect` header
 #[derive(Fail, PartialEq, Debug)]
 pub enum ExpectError {
@@ -505,6 +611,27 @@ impl ResponseError for UriSegmentError {
     }
 }
 
+/// Errors which can occur when serving static files.
+#[derive(Fail, Debug, PartialEq)]
+pub enum StaticFileError {
+    /// Request path is not valid utf-8 or contains invalid characters.
+    #[fail(display = "Request path is not valid")]
+    InvalidPath,
+    /// Access to the file is forbidden by `StaticFiles` policy.
+    #[fail(display = "Access to the file is forbidden")]
+    Forbidden,
+}
+
+/// Return `BadRequest` or `Forbidden` for `StaticFileError`
+impl ResponseError for StaticFileError {
+    fn error_response(&self) -> HttpResponse {
+        match *self {
+            StaticFileError::InvalidPath => HttpResponse::new(StatusCode::BAD_REQUEST),
+            StaticFileError::Forbidden => HttpResponse::new(StatusCode::FORBIDDEN),
+        }
+    }
+}
+
 /// Errors which can occur when attempting to generate resource uri.
 #[derive(Fail, Debug, PartialEq)]
 pub enum UrlGenerationError {
@@ -1,19 +1,29 @@
+use std::env;
+use std::io;
 use std::ops::{Deref, DerefMut};
+use std::path::PathBuf;
+use std::rc::Rc;
 use std::str;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use encoding::all::UTF_8;
 use encoding::types::{DecoderTrap, Encoding};
-use futures::future::{result, Future, FutureResult};
+use futures::future::{err, ok, result, Future, FutureResult};
+use futures::{Async, Poll, Stream};
+use futures_cpupool::CpuPool;
 use mime::Mime;
 use serde::de::{self, DeserializeOwned};
 use serde_urlencoded;
+use url::form_urlencoded;
 
 use de::PathDeserializer;
-use error::{Error, ErrorBadRequest};
+use error::{Error, ErrorBadRequest, HeaderError, MultipartFormError, UrlencodedError};
 use handler::{Either, FromRequest};
+use header;
+use http::Version;
 use httpmessage::{HttpMessage, MessageBody, UrlEncoded};
 use httprequest::HttpRequest;
+use multipart::{Field, MultipartFile, MultipartItem, TempFile};
 
 /// Extract typed information from the request's path.
 ///
@@ -65,6 +75,13 @@ use httprequest::HttpRequest;
 ///        |r| r.method(http::Method::GET).with(index));  // <- use `with` extractor
 /// }
 /// ```
+///
+/// Tail parameter could be extracted to a
+/// [`PathTail`](struct.PathTail.html), that is safe to join to a base
+/// directory.
+///
+/// [**PathConfig**](dev/struct.PathConfig.html) allows to set custom
+/// error handler.
 pub struct Path<T> {
     inner: T,
 }
@@ -101,20 +118,72 @@ where
     T: DeserializeOwned,
     S: 'static,
 {
-    type Config = ();
+    type Config = PathConfig<S>;
     type Result = FutureResult<Self, Error>;
 
     #[inline]
-    fn from_request(req: &HttpRequest<S>, _: &Self::Config) -> Self::Result {
+    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
         let req = req.clone();
         result(
             de::Deserialize::deserialize(PathDeserializer::new(&req))
-                .map_err(|e| e.into())
+                .map_err(|e| (*cfg.ehandler)(e, req.clone()))
                 .map(|inner| Path { inner }),
         )
     }
 }
 
+/// Path extractor configuration
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::{error, http, App, HttpResponse, Path, Result};
+///
+/// fn index(info: Path<(u32, String)>) -> Result<String> {
+///     Ok(format!("Welcome {}!", info.1))
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource("/index.html/{id}/{name}", |r| {
+///         r.method(http::Method::GET)
+///             .with(index)
+///             .error_handler(|err, req| {  // <- create custom error response
+///                 error::InternalError::from_response(
+///                     err, HttpResponse::Conflict().finish()).into()
+///             });
+///     });
+/// }
+/// ```
+pub struct PathConfig<S> {
+    ehandler: Rc<Fn(de::value::Error, HttpRequest<S>) -> Error>,
+}
+
+impl<S> PathConfig<S> {
+    /// Set custom error handler
+    pub fn error_handler<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(de::value::Error, HttpRequest<S>) -> Error + 'static,
+    {
+        self.ehandler = Rc::new(f);
+        self
+    }
+}
+
+impl<S> Default for PathConfig<S> {
+    fn default() -> Self {
+        PathConfig {
+            ehandler: Rc::new(|e, req| req.extractor_error(e.into())),
+        }
+    }
+}
+
+impl<S> Clone for PathConfig<S> {
+    fn clone(&self) -> Self {
+        PathConfig {
+            ehandler: Rc::clone(&self.ehandler),
+        }
+    }
+}
+
 /// Extract typed information from from the request's query.
 ///
 /// ## Example
@@ -143,6 +212,9 @@ where
 ///        |r| r.method(http::Method::GET).with(index)); // <- use `with` extractor
 /// }
 /// ```
+///
+/// [**QueryConfig**](dev/struct.QueryConfig.html) allows to set custom
+/// error handler.
 pub struct Query<T>(T);
 
 impl<T> Deref for Query<T> {
@@ -171,18 +243,81 @@ where
     T: de::DeserializeOwned,
     S: 'static,
 {
-    type Config = ();
+    type Config = QueryConfig<S>;
     type Result = FutureResult<Self, Error>;
 
     #[inline]
-    fn from_request(req: &HttpRequest<S>, _: &Self::Config) -> Self::Result {
+    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
         let req = req.clone();
         result(
             serde_urlencoded::from_str::<T>(req.query_string())
-                .map_err(|e| e.into())
+                .map_err(|e| (*cfg.ehandler)(e, req.clone()))
                 .map(Query),
         )
     }
+
+    #[inline]
+    fn is_absent(req: &HttpRequest<S>, _: &Self::Config) -> bool {
+        req.query_string().is_empty()
+    }
+}
+
+/// Query extractor configuration
+///
+/// ```rust
+/// # extern crate actix_web;
+/// #[macro_use] extern crate serde_derive;
+/// use actix_web::{error, http, App, HttpResponse, Query, Result};
+///
+/// #[derive(Deserialize)]
+/// struct Info {
+///     username: String,
+/// }
+///
+/// fn index(info: Query<Info>) -> Result<String> {
+///     Ok(format!("Welcome {}!", info.username))
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource("/index.html", |r| {
+///         r.method(http::Method::GET)
+///             .with(index)
+///             .error_handler(|err, req| {  // <- create custom error response
+///                 error::InternalError::from_response(
+///                     err, HttpResponse::Conflict().finish()).into()
+///             });
+///     });
+/// }
+/// ```
+pub struct QueryConfig<S> {
+    ehandler: Rc<Fn(de::value::Error, HttpRequest<S>) -> Error>,
+}
+
+impl<S> QueryConfig<S> {
+    /// Set custom error handler
+    pub fn error_handler<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(de::value::Error, HttpRequest<S>) -> Error + 'static,
+    {
+        self.ehandler = Rc::new(f);
+        self
+    }
+}
+
+impl<S> Default for QueryConfig<S> {
+    fn default() -> Self {
+        QueryConfig {
+            ehandler: Rc::new(|e, req| req.extractor_error(e.into())),
+        }
+    }
+}
+
+impl<S> Clone for QueryConfig<S> {
+    fn clone(&self) -> Self {
+        QueryConfig {
+            ehandler: Rc::clone(&self.ehandler),
+        }
+    }
 }
 
 /// Extract typed information from the request's body.
@@ -244,18 +379,25 @@ where
     T: DeserializeOwned + 'static,
     S: 'static,
 {
-    type Config = FormConfig;
+    type Config = FormConfig<S>;
     type Result = Box<Future<Item = Self, Error = Error>>;
 
     #[inline]
     fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
+        let req = req.clone();
+        let err = Rc::clone(&cfg.ehandler);
         Box::new(
             UrlEncoded::new(req.clone())
                 .limit(cfg.limit)
-                .from_err()
+                .map_err(move |e| (*err)(e, req))
                 .map(Form),
         )
     }
+
+    #[inline]
+    fn is_absent(req: &HttpRequest<S>, _: &Self::Config) -> bool {
+        payload_is_absent(req)
+    }
 }
 
 /// Form extractor configuration
@@ -285,209 +427,1067 @@ where
 ///     );
 /// }
 /// ```
-pub struct FormConfig {
+pub struct FormConfig<S> {
     limit: usize,
+    ehandler: Rc<Fn(UrlencodedError, HttpRequest<S>) -> Error>,
 }
 
-impl FormConfig {
+impl<S> FormConfig<S> {
     /// Change max size of payload. By default max size is 256Kb
     pub fn limit(&mut self, limit: usize) -> &mut Self {
         self.limit = limit;
         self
     }
+
+    /// Set custom error handler
+    pub fn error_handler<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(UrlencodedError, HttpRequest<S>) -> Error + 'static,
+    {
+        self.ehandler = Rc::new(f);
+        self
+    }
 }
 
-impl Default for FormConfig {
+impl<S> Default for FormConfig<S> {
     fn default() -> Self {
-        FormConfig { limit: 262_144 }
+        FormConfig {
+            limit: 262_144,
+            ehandler: Rc::new(|e, req| req.extractor_error(e.into())),
+        }
     }
 }
 
-/// Request payload extractor.
-///
-/// Loads request's payload and construct Bytes instance.
-///
-/// [**PayloadConfig**](dev/struct.PayloadConfig.html) allows to configure
-/// extraction process.
-///
-/// ## Example
-///
-/// ```rust
-/// extern crate bytes;
-/// # extern crate actix_web;
-/// use actix_web::{App, Result};
-///
-/// /// extract text data from request
-/// fn index(body: bytes::Bytes) -> Result<String> {
-///     Ok(format!("Body {:?}!", body))
-/// }
-/// # fn main() {}
-/// ```
-impl<S: 'static> FromRequest<S> for Bytes {
-    type Config = PayloadConfig;
-    type Result =
-        Either<FutureResult<Self, Error>, Box<Future<Item = Self, Error = Error>>>;
-
-    #[inline]
-    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
-        // check content-type
-        if let Err(e) = cfg.check_mimetype(req) {
-            return Either::A(result(Err(e)));
+impl<S> Clone for FormConfig<S> {
+    fn clone(&self) -> Self {
+        FormConfig {
+            limit: self.limit,
+            ehandler: Rc::clone(&self.ehandler),
         }
-
-        Either::B(Box::new(
-            MessageBody::new(req.clone())
-                .limit(cfg.limit)
-                .from_err(),
-        ))
     }
 }
 
-/// Extract text information from the request's body.
+/// Extract typed information from the request's `multipart/form-data` body.
 ///
-/// Text extractor automatically decode body according to the request's charset.
+/// Text fields are deserialized into `T`, the same way as urlencoded
+/// form fields. File fields (fields with `filename` parameter) are available
+/// as `MultipartFile`s, small files are kept in memory, larger files are
+/// spooled to temporary files.
 ///
-/// [**PayloadConfig**](dev/struct.PayloadConfig.html) allows to configure
-/// extraction process.
+/// [**MultipartFormConfig**](dev/struct.MultipartFormConfig.html) allows to
+/// configure size and field count limits. If a limit is exceeded, request
+/// gets rejected with `413 Payload Too Large` response.
 ///
 /// ## Example
 ///
 /// ```rust
 /// # extern crate actix_web;
-/// use actix_web::{App, Result};
+/// #[macro_use] extern crate serde_derive;
+/// use actix_web::{App, MultipartForm, Result, http};
 ///
-/// /// extract text data from request
-/// fn index(body: String) -> Result<String> {
-///     Ok(format!("Body {}!", body))
+/// #[derive(Deserialize)]
+/// struct Upload {
+///     title: String,
+/// }
+///
+/// /// store uploaded file
+/// fn upload(form: MultipartForm<Upload>) -> Result<String> {
+///     match form.file("file") {
+///         Some(file) => Ok(format!("{}: {} bytes", form.title, file.size())),
+///         None => Ok(format!("{}: no file", form.title)),
+///     }
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource(
+///        "/upload", |r| {
+///            r.method(http::Method::POST)
+///              .with(upload)
+///              .file_limit(1_048_576);} // <- max file size is 1Mb
+///     );
 /// }
-/// # fn main() {}
 /// ```
-impl<S: 'static> FromRequest<S> for String {
-    type Config = PayloadConfig;
-    type Result =
-        Either<FutureResult<String, Error>, Box<Future<Item = String, Error = Error>>>;
+pub struct MultipartForm<T> {
+    data: T,
+    files: Vec<MultipartFile>,
+}
 
-    #[inline]
-    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
-        // check content-type
-        if let Err(e) = cfg.check_mimetype(req) {
-            return Either::A(result(Err(e)));
-        }
+impl<T> MultipartForm<T> {
+    /// Deconstruct to an inner value
+    pub fn into_inner(self) -> T {
+        self.data
+    }
 
-        // check charset
-        let encoding = match req.encoding() {
-            Err(_) => {
-                return Either::A(result(Err(ErrorBadRequest(
-                    "Unknown request charset",
-                ))))
-            }
-            Ok(encoding) => encoding,
-        };
+    /// Deconstruct to an inner value and uploaded files
+    pub fn into_parts(self) -> (T, Vec<MultipartFile>) {
+        (self.data, self.files)
+    }
 
-        Either::B(Box::new(
-            MessageBody::new(req.clone())
-                .limit(cfg.limit)
-                .from_err()
-                .and_then(move |body| {
-                    let enc: *const Encoding = encoding as *const Encoding;
-                    if enc == UTF_8 {
-                        Ok(str::from_utf8(body.as_ref())
-                            .map_err(|_| ErrorBadRequest("Can not decode body"))?
-                            .to_owned())
-                    } else {
-                        Ok(encoding
-                            .decode(&body, DecoderTrap::Strict)
-                            .map_err(|_| ErrorBadRequest("Can not decode body"))?)
-                    }
-                }),
-        ))
+    /// All uploaded files
+    pub fn files(&self) -> &[MultipartFile] {
+        &self.files
     }
-}
 
-/// Payload configuration for request's payload.
-pub struct PayloadConfig {
-    limit: usize,
-    mimetype: Option<Mime>,
+    /// First uploaded file for field `name`
+    pub fn file(&self, name: &str) -> Option<&MultipartFile> {
+        self.files.iter().find(|f| f.field_name() == name)
+    }
 }
 
-impl PayloadConfig {
-    /// Change max size of payload. By default max size is 256Kb
-    pub fn limit(&mut self, limit: usize) -> &mut Self {
-        self.limit = limit;
-        self
+impl<T> Deref for MultipartForm<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.data
     }
+}
 
-    /// Set required mime-type of the request. By default mime type is not
-    /// enforced.
-    pub fn mimetype(&mut self, mt: Mime) -> &mut Self {
-        self.mimetype = Some(mt);
-        self
+impl<T> DerefMut for MultipartForm<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.data
     }
+}
 
-    fn check_mimetype<S>(&self, req: &HttpRequest<S>) -> Result<(), Error> {
-        // check content-type
-        if let Some(ref mt) = self.mimetype {
-            match req.mime_type() {
-                Ok(Some(ref req_mt)) => {
-                    if mt != req_mt {
-                        return Err(ErrorBadRequest("Unexpected Content-Type"));
+impl<T, S> FromRequest<S> for MultipartForm<T>
+where
+    T: DeserializeOwned + 'static,
+    S: 'static,
+{
+    type Config = MultipartFormConfig<S>;
+    type Result = Box<Future<Item = Self, Error = Error>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
+        let req = req.clone();
+        let ehandler = Rc::clone(&cfg.ehandler);
+        let state = FormState {
+            cfg: cfg.clone(),
+            pool: cfg.cpu_pool
+                .clone()
+                .unwrap_or_else(|| req.cpu_pool().clone()),
+            fields: Vec::new(),
+            files: Vec::new(),
+            total: 0,
+        };
+
+        Box::new(
+            req.clone()
+                .multipart()
+                .map_err(MultipartFormError::from)
+                .fold(state, |state, item| -> FieldFuture<S> {
+                    match item {
+                        MultipartItem::Field(field) => state.field(field),
+                        MultipartItem::Nested(_) => {
+                            Box::new(err(MultipartFormError::Nested))
+                        }
                     }
-                }
-                Ok(None) => {
-                    return Err(ErrorBadRequest("Content-Type is expected"));
-                }
-                Err(err) => {
-                    return Err(err.into());
-                }
-            }
-        }
-        Ok(())
+                })
+                .and_then(|state| {
+                    let mut query = form_urlencoded::Serializer::new(String::new());
+                    for &(ref name, ref value) in &state.fields {
+                        query.append_pair(name, value);
+                    }
+                    let data = serde_urlencoded::from_str::<T>(&query.finish())?;
+                    Ok(MultipartForm {
+                        data,
+                        files: state.files,
+                    })
+                })
+                .map_err(move |e| (*ehandler)(e, req)),
+        )
+    }
+
+    #[inline]
+    fn is_absent(req: &HttpRequest<S>, _: &Self::Config) -> bool {
+        payload_is_absent(req)
     }
 }
 
-impl Default for PayloadConfig {
-    fn default() -> Self {
-        PayloadConfig {
-            limit: 262_144,
-            mimetype: None,
+type FieldFuture<S> = Box<Future<Item = FormState<S>, Error = MultipartFormError>>;
+
+struct FormState<S> {
+    cfg: MultipartFormConfig<S>,
+    pool: CpuPool,
+    fields: Vec<(String, String)>,
+    files: Vec<MultipartFile>,
+    total: usize,
+}
+
+impl<S: 'static> FormState<S> {
+    fn field(self, field: Field<HttpRequest<S>>) -> FieldFuture<S> {
+        if self.fields.len() + self.files.len() >= self.cfg.max_fields {
+            return Box::new(err(MultipartFormError::TooManyFields));
         }
+        let name = match field.field_name() {
+            Some(name) => name,
+            None => return Box::new(err(MultipartFormError::FieldName)),
+        };
+        let state = FieldState {
+            file_name: field.file_name(),
+            content_type: field.content_type().clone(),
+            form: self,
+            name,
+            buf: BytesMut::new(),
+            file: None,
+            size: 0,
+        };
+
+        Box::new(
+            field
+                .map_err(MultipartFormError::from)
+                .fold(state, FieldState::feed)
+                .and_then(FieldState::finish),
+        )
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use bytes::Bytes;
-    use futures::{Async, Future};
-    use http::header;
-    use mime;
-    use resource::ResourceHandler;
-    use router::{Resource, Router};
-    use server::ServerSettings;
-    use test::TestRequest;
+struct FieldState<S> {
+    form: FormState<S>,
+    name: String,
+    file_name: Option<String>,
+    content_type: Mime,
+    buf: BytesMut,
+    file: Option<TempFile>,
+    size: usize,
+}
 
-    #[derive(Deserialize, Debug, PartialEq)]
-    struct Info {
-        hello: String,
-    }
+type FieldFeed<S> = Box<Future<Item = FieldState<S>, Error = MultipartFormError>>;
 
-    #[test]
-    fn test_bytes() {
-        let cfg = PayloadConfig::default();
-        let mut req = TestRequest::with_header(header::CONTENT_LENGTH, "11").finish();
-        req.payload_mut()
-            .unread_data(Bytes::from_static(b"hello=world"));
+impl<S: 'static> FieldState<S> {
+    fn feed(mut self, chunk: Bytes) -> FieldFeed<S> {
+        let limit = if self.file_name.is_some() {
+            self.form.cfg.file_limit
+        } else {
+            self.form.cfg.field_limit
+        };
 
-        match Bytes::from_request(&req, &cfg).poll().unwrap() {
-            Async::Ready(s) => {
-                assert_eq!(s, Bytes::from_static(b"hello=world"));
-            }
-            _ => unreachable!(),
+        self.size += chunk.len();
+        self.form.total += chunk.len();
+        if self.size > limit {
+            return Box::new(err(MultipartFormError::FieldOverflow(self.name.clone())));
+        }
+        if self.form.total > self.form.cfg.total_limit {
+            return Box::new(err(MultipartFormError::Overflow));
         }
-    }
 
-    #[test]
-    fn test_string() {
+        // spool large files to disk, file operations run on cpu pool
+        let mut file = if let Some(file) = self.file.take() {
+            file
+        } else if self.file_name.is_some()
+            && self.buf.len() + chunk.len() > self.form.cfg.memory_threshold
+        {
+            let dir = self.form
+                .cfg
+                .temp_dir
+                .clone()
+                .unwrap_or_else(env::temp_dir);
+            let buf = self.buf.take().freeze();
+            let fut = self.form.pool.spawn_fn(move || -> io::Result<TempFile> {
+                let mut file = TempFile::new(&dir)?;
+                file.write(&buf)?;
+                file.write(&chunk)?;
+                Ok(file)
+            });
+            return Box::new(fut.map_err(MultipartFormError::from).map(
+                move |file| {
+                    self.file = Some(file);
+                    self
+                },
+            ));
+        } else {
+            self.buf.extend_from_slice(&chunk);
+            return Box::new(ok(self));
+        };
+
+        let fut = self.form.pool.spawn_fn(move || -> io::Result<TempFile> {
+            file.write(&chunk)?;
+            Ok(file)
+        });
+        Box::new(fut.map_err(MultipartFormError::from).map(move |file| {
+            self.file = Some(file);
+            self
+        }))
+    }
+
+    fn finish(self) -> Result<FormState<S>, MultipartFormError> {
+        let FieldState {
+            mut form,
+            name,
+            file_name,
+            content_type,
+            buf,
+            file,
+            size,
+        } = self;
+
+        if let Some(file) = file {
+            form.files.push(MultipartFile::spooled(
+                name,
+                file_name,
+                content_type,
+                size,
+                file,
+            ));
+        } else if file_name.is_some() {
+            form.files.push(MultipartFile::memory(
+                name,
+                file_name,
+                content_type,
+                buf.freeze(),
+            ));
+        } else {
+            let value = match String::from_utf8(buf.to_vec()) {
+                Ok(value) => value,
+                Err(_) => return Err(MultipartFormError::FieldEncoding(name)),
+            };
+            form.fields.push((name, value));
+        }
+        Ok(form)
+    }
+}
+
+/// Multipart form extractor configuration
+///
+/// ```rust
+/// # extern crate actix_web;
+/// #[macro_use] extern crate serde_derive;
+/// use actix_web::{App, MultipartForm, Result, http};
+///
+/// #[derive(Deserialize)]
+/// struct Upload {
+///     title: String,
+/// }
+///
+/// fn upload(form: MultipartForm<Upload>) -> Result<String> {
+///     Ok(format!("{}: {} files", form.title, form.files().len()))
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource(
+///        "/upload", |r| {
+///            r.method(http::Method::POST)
+///              .with(upload)
+///              .max_fields(10)
+///              .total_limit(10_485_760)
+///              .temp_dir("/var/tmp");} // <- change multipart form configuration
+///     );
+/// }
+/// ```
+pub struct MultipartFormConfig<S> {
+    field_limit: usize,
+    file_limit: usize,
+    total_limit: usize,
+    max_fields: usize,
+    memory_threshold: usize,
+    temp_dir: Option<PathBuf>,
+    cpu_pool: Option<CpuPool>,
+    ehandler: Rc<Fn(MultipartFormError, HttpRequest<S>) -> Error>,
+}
+
+impl<S> MultipartFormConfig<S> {
+    /// Change max size of a text field. By default max size is 256Kb
+    pub fn field_limit(&mut self, limit: usize) -> &mut Self {
+        self.field_limit = limit;
+        self
+    }
+
+    /// Change max size of a file. By default max size is 10Mb
+    pub fn file_limit(&mut self, limit: usize) -> &mut Self {
+        self.file_limit = limit;
+        self
+    }
+
+    /// Change max size of all fields. By default max size is 50Mb
+    pub fn total_limit(&mut self, limit: usize) -> &mut Self {
+        self.total_limit = limit;
+        self
+    }
+
+    /// Change max number of fields. By default max number is 100
+    pub fn max_fields(&mut self, max: usize) -> &mut Self {
+        self.max_fields = max;
+        self
+    }
+
+    /// Files larger than threshold are spooled to disk. By default
+    /// threshold is 256Kb
+    pub fn memory_threshold(&mut self, threshold: usize) -> &mut Self {
+        self.memory_threshold = threshold;
+        self
+    }
+
+    /// Set directory for temporary files. By default system's temporary
+    /// directory is used
+    pub fn temp_dir<P: Into<PathBuf>>(&mut self, dir: P) -> &mut Self {
+        self.temp_dir = Some(dir.into());
+        self
+    }
+
+    /// Set `CpuPool` for writing spooled files. By default server's
+    /// `CpuPool` is used
+    pub fn cpu_pool(&mut self, pool: CpuPool) -> &mut Self {
+        self.cpu_pool = Some(pool);
+        self
+    }
+
+    /// Set custom error handler
+    pub fn error_handler<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(MultipartFormError, HttpRequest<S>) -> Error + 'static,
+    {
+        self.ehandler = Rc::new(f);
+        self
+    }
+}
+
+impl<S> Default for MultipartFormConfig<S> {
+    fn default() -> Self {
+        MultipartFormConfig {
+            field_limit: 262_144,
+            file_limit: 10_485_760,
+            total_limit: 52_428_800,
+            max_fields: 100,
+            memory_threshold: 262_144,
+            temp_dir: None,
+            cpu_pool: None,
+            ehandler: Rc::new(|e, req| req.extractor_error(e.into())),
+        }
+    }
+}
+
+impl<S> Clone for MultipartFormConfig<S> {
+    fn clone(&self) -> Self {
+        MultipartFormConfig {
+            field_limit: self.field_limit,
+            file_limit: self.file_limit,
+            total_limit: self.total_limit,
+            max_fields: self.max_fields,
+            memory_threshold: self.memory_threshold,
+            temp_dir: self.temp_dir.clone(),
+            cpu_pool: self.cpu_pool.clone(),
+            ehandler: Rc::clone(&self.ehandler),
+        }
+    }
+}
+
+/// Extract typed header from the request.
+///
+/// Header type must implement `header::Header` trait, all typed headers from
+/// the `http::header` module could be used. Malformed header is rejected with
+/// *400 Bad Request* response. Use `Option<Header<H>>` for optional headers,
+/// it is `None` if header is not set, malformed header is rejected as well.
+///
+/// [**HeaderConfig**](dev/struct.HeaderConfig.html) allows to set custom
+/// error handler, i.e. for missing header.
+///
+/// ## Example
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::http::header::{ContentType, IfModifiedSince};
+/// use actix_web::{http, App, Header, Result};
+///
+/// fn index(
+///     (ct, since): (Header<ContentType>, Option<Header<IfModifiedSince>>)
+/// ) -> Result<String> {
+///     Ok(format!("Content type {}, since {:?}", *ct, since.map(|h| h.into_inner())))
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource("/index.html", |r| {
+///         r.method(http::Method::POST).with(index)
+///     });
+/// }
+/// ```
+pub struct Header<H>(pub H);
+
+impl<H> Header<H> {
+    /// Deconstruct to an inner value
+    pub fn into_inner(self) -> H {
+        self.0
+    }
+}
+
+impl<H> Deref for Header<H> {
+    type Target = H;
+
+    fn deref(&self) -> &H {
+        &self.0
+    }
+}
+
+impl<H> DerefMut for Header<H> {
+    fn deref_mut(&mut self) -> &mut H {
+        &mut self.0
+    }
+}
+
+impl<H, S> FromRequest<S> for Header<H>
+where
+    H: header::Header,
+    S: 'static,
+{
+    type Config = HeaderConfig<S>;
+    type Result = FutureResult<Self, Error>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
+        let name = H::name();
+        if !req.headers().contains_key(&name) {
+            return result(Err((*cfg.ehandler)(
+                HeaderError::Missing(name),
+                req.clone(),
+            )));
+        }
+        result(
+            H::parse(req)
+                .map(Header)
+                .map_err(|_| (*cfg.ehandler)(HeaderError::Malformed(name), req.clone())),
+        )
+    }
+
+    #[inline]
+    fn is_absent(req: &HttpRequest<S>, _: &Self::Config) -> bool {
+        !req.headers().contains_key(H::name())
+    }
+}
+
+/// Header extractor configuration
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::error::{ErrorUnauthorized, HeaderError};
+/// use actix_web::http::header::ContentType;
+/// use actix_web::{http, App, Header};
+///
+/// fn index(ct: Header<ContentType>) -> String {
+///     format!("Content type {}", *ct)
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource("/index.html", |r| {
+///         r.method(http::Method::POST)
+///             .with(index)
+///             .error_handler(|err, _| match err {
+///                 HeaderError::Missing(name) => {
+///                     ErrorUnauthorized(format!("{} is required", name))
+///                 }
+///                 err => err.into(),
+///             });
+///     });
+/// }
+/// ```
+pub struct HeaderConfig<S> {
+    ehandler: Rc<Fn(HeaderError, HttpRequest<S>) -> Error>,
+}
+
+impl<S> HeaderConfig<S> {
+    /// Set custom error handler. By default missing and malformed headers
+    /// are rejected with *400 Bad Request* response.
+    pub fn error_handler<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(HeaderError, HttpRequest<S>) -> Error + 'static,
+    {
+        self.ehandler = Rc::new(f);
+        self
+    }
+}
+
+impl<S> Default for HeaderConfig<S> {
+    fn default() -> Self {
+        HeaderConfig {
+            ehandler: Rc::new(|e, req| req.extractor_error(e.into())),
+        }
+    }
+}
+
+impl<S> Clone for HeaderConfig<S> {
+    fn clone(&self) -> Self {
+        HeaderConfig {
+            ehandler: Rc::clone(&self.ehandler),
+        }
+    }
+}
+
+/// Request payload extractor.
+///
+/// Loads request's payload and construct Bytes instance.
+///
+/// [**PayloadConfig**](dev/struct.PayloadConfig.html) allows to configure
+/// extraction process.
+///
+/// ## Example
+///
+/// ```rust
+/// extern crate bytes;
+/// # extern crate actix_web;
+/// use actix_web::{App, Result};
+///
+/// /// extract text data from request
+/// fn index(body: bytes::Bytes) -> Result<String> {
+///     Ok(format!("Body {:?}!", body))
+/// }
+/// # fn main() {}
+/// ```
+impl<S: 'static> FromRequest<S> for Bytes {
+    type Config = PayloadConfig<S>;
+    type Result =
+        Either<FutureResult<Self, Error>, Box<Future<Item = Self, Error = Error>>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
+        // check content-type
+        if let Err(e) = cfg.check_mimetype(req) {
+            return Either::A(result(Err((*cfg.ehandler)(e, req.clone()))));
+        }
+
+        let req = req.clone();
+        let err = Rc::clone(&cfg.ehandler);
+        Either::B(Box::new(
+            MessageBody::new(req.clone())
+                .limit(cfg.limit)
+                .map_err(move |e| (*err)(e.into(), req)),
+        ))
+    }
+}
+
+/// Extract text information from the request's body.
+///
+/// Text extractor automatically decode body according to the request's charset.
+///
+/// [**PayloadConfig**](dev/struct.PayloadConfig.html) allows to configure
+/// extraction process.
+///
+/// ## Example
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::{App, Result};
+///
+/// /// extract text data from request
+/// fn index(body: String) -> Result<String> {
+///     Ok(format!("Body {}!", body))
+/// }
+/// # fn main() {}
+/// ```
+impl<S: 'static> FromRequest<S> for String {
+    type Config = PayloadConfig<S>;
+    type Result =
+        Either<FutureResult<String, Error>, Box<Future<Item = String, Error = Error>>>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
+        // check content-type
+        if let Err(e) = cfg.check_mimetype(req) {
+            return Either::A(result(Err((*cfg.ehandler)(e, req.clone()))));
+        }
+
+        // check charset
+        let encoding = match req.encoding() {
+            Err(_) => {
+                return Either::A(result(Err((*cfg.ehandler)(
+                    ErrorBadRequest("Unknown request charset"),
+                    req.clone(),
+                ))))
+            }
+            Ok(encoding) => encoding,
+        };
+
+        let req = req.clone();
+        let err = Rc::clone(&cfg.ehandler);
+        Either::B(Box::new(
+            MessageBody::new(req.clone())
+                .limit(cfg.limit)
+                .from_err()
+                .and_then(move |body| {
+                    let enc: *const Encoding = encoding as *const Encoding;
+                    if enc == UTF_8 {
+                        Ok(str::from_utf8(body.as_ref())
+                            .map_err(|_| ErrorBadRequest("Can not decode body"))?
+                            .to_owned())
+                    } else {
+                        Ok(encoding
+                            .decode(&body, DecoderTrap::Strict)
+                            .map_err(|_| ErrorBadRequest("Can not decode body"))?)
+                    }
+                })
+                .map_err(move |e| (*err)(e, req)),
+        ))
+    }
+}
+
+/// Payload configuration for request's payload.
+pub struct PayloadConfig<S> {
+    limit: usize,
+    mimetype: Option<Mime>,
+    ehandler: Rc<Fn(Error, HttpRequest<S>) -> Error>,
+}
+
+impl<S> PayloadConfig<S> {
+    /// Change max size of payload. By default max size is 256Kb
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Set required mime-type of the request. By default mime type is not
+    /// enforced.
+    pub fn mimetype(&mut self, mt: Mime) -> &mut Self {
+        self.mimetype = Some(mt);
+        self
+    }
+
+    /// Set custom error handler
+    pub fn error_handler<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(Error, HttpRequest<S>) -> Error + 'static,
+    {
+        self.ehandler = Rc::new(f);
+        self
+    }
+
+    fn check_mimetype(&self, req: &HttpRequest<S>) -> Result<(), Error> {
+        // check content-type
+        if let Some(ref mt) = self.mimetype {
+            match req.mime_type() {
+                Ok(Some(ref req_mt)) => {
+                    if mt != req_mt {
+                        return Err(ErrorBadRequest("Unexpected Content-Type"));
+                    }
+                }
+                Ok(None) => {
+                    return Err(ErrorBadRequest("Content-Type is expected"));
+                }
+                Err(err) => {
+                    return Err(err.into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S> Default for PayloadConfig<S> {
+    fn default() -> Self {
+        PayloadConfig {
+            limit: 262_144,
+            mimetype: None,
+            ehandler: Rc::new(|e, req| req.extractor_error(e)),
+        }
+    }
+}
+
+impl<S> Clone for PayloadConfig<S> {
+    fn clone(&self) -> Self {
+        PayloadConfig {
+            limit: self.limit,
+            mimetype: self.mimetype.clone(),
+            ehandler: Rc::clone(&self.ehandler),
+        }
+    }
+}
+
+/// Check if request has no body. Body of http/2 request is never
+/// considered absent, because content length is optional there.
+pub(crate) fn payload_is_absent<S>(req: &HttpRequest<S>) -> bool {
+    if let Some(len) = req.headers().get(header::CONTENT_LENGTH) {
+        return len.as_bytes() == b"0";
+    }
+    req.version() != Version::HTTP_2
+        && !req.headers().contains_key(header::TRANSFER_ENCODING)
+}
+
+/// Optionally extract a field from the request
+///
+/// Handler receives `None` if the value is absent from the request, i.e.
+/// optional header is not set, request has no body or query string is
+/// empty. Other errors, like malformed header or invalid json, are
+/// returned as usual. `Path`, `Bytes` and `String` extractors are never
+/// absent, so `Option` of them is always `Some`. Use `Result<T, Error>`
+/// to handle all extraction errors in the handler.
+///
+/// ## Example
+///
+/// ```rust
+/// # extern crate actix_web;
+/// #[macro_use] extern crate serde_derive;
+/// use actix_web::{http, App, Json};
+///
+/// #[derive(Deserialize)]
+/// struct Info {
+///     username: String,
+/// }
+///
+/// /// json body is optional
+/// fn index(info: Option<Json<Info>>) -> String {
+///     match info {
+///         Some(info) => format!("Welcome {}!", info.username),
+///         None => "Welcome!".to_owned(),
+///     }
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource("/index.html", |r| {
+///         r.method(http::Method::POST).with(index)
+///     });
+/// }
+/// ```
+impl<T, S> FromRequest<S> for Option<T>
+where
+    T: FromRequest<S>,
+    S: 'static,
+{
+    type Config = T::Config;
+    type Result = OptionFromRequest<S, T>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
+        if T::is_absent(req, cfg) {
+            OptionFromRequest { fut: None }
+        } else {
+            OptionFromRequest {
+                fut: Some(T::from_request(req, cfg)),
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct OptionFromRequest<S: 'static, T: FromRequest<S>> {
+    fut: Option<T::Result>,
+}
+
+impl<S: 'static, T: FromRequest<S>> Future for OptionFromRequest<S, T> {
+    type Item = Option<T>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.fut {
+            Some(ref mut fut) => Ok(fut.poll()?.map(Some)),
+            None => Ok(Async::Ready(None)),
+        }
+    }
+}
+
+/// Extract a field from the request, extraction error is passed to
+/// the handler instead of being returned as a response.
+///
+/// ## Example
+///
+/// ```rust
+/// # extern crate actix_web;
+/// #[macro_use] extern crate serde_derive;
+/// use actix_web::{http, App, Error, HttpResponse, Query};
+///
+/// #[derive(Deserialize)]
+/// struct Info {
+///     username: String,
+/// }
+///
+/// fn index(info: Result<Query<Info>, Error>) -> HttpResponse {
+///     match info {
+///         Ok(info) => HttpResponse::Ok().body(format!("Welcome {}!", info.username)),
+///         Err(err) => HttpResponse::BadRequest().body(format!("Bad query: {}", err)),
+///     }
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource("/index.html", |r| {
+///         r.method(http::Method::GET).with(index)
+///     });
+/// }
+/// ```
+impl<T, S> FromRequest<S> for Result<T, Error>
+where
+    T: FromRequest<S>,
+    S: 'static,
+{
+    type Config = T::Config;
+    type Result = ResultFromRequest<S, T>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
+        ResultFromRequest {
+            fut: T::from_request(req, cfg),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct ResultFromRequest<S: 'static, T: FromRequest<S>> {
+    fut: T::Result,
+}
+
+impl<S: 'static, T: FromRequest<S>> Future for ResultFromRequest<S, T> {
+    type Item = Result<T, Error>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.fut.poll() {
+            Ok(Async::Ready(item)) => Ok(Async::Ready(Ok(item))),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(err) => Ok(Async::Ready(Err(err))),
+        }
+    }
+}
+
+macro_rules! tuple_from_req ({$fut_type:ident, $(($n:tt, $T:ident)),+} => {
+
+    /// Extract a tuple of values from the request.
+    ///
+    /// Extractors run one after another, next extractor starts only when
+    /// previous one is resolved. `Config` is a tuple of extractors' configs.
+    impl<S: 'static, $($T: FromRequest<S> + 'static),+> FromRequest<S> for ($($T,)+)
+    where
+        $($T::Config: Clone),+
+    {
+        type Config = ($($T::Config,)+);
+        type Result = $fut_type<S, $($T),+>;
+
+        fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
+            $fut_type {
+                req: req.clone(),
+                cfg: cfg.clone(),
+                items: <($(Option<$T>,)+)>::default(),
+                futs: <($(Option<$T::Result>,)+)>::default(),
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    pub struct $fut_type<S: 'static, $($T: FromRequest<S>),+> {
+        req: HttpRequest<S>,
+        cfg: ($($T::Config,)+),
+        items: ($(Option<$T>,)+),
+        futs: ($(Option<$T::Result>,)+),
+    }
+
+    impl<S: 'static, $($T: FromRequest<S>),+> Future for $fut_type<S, $($T),+>
+    {
+        type Item = ($($T,)+);
+        type Error = Error;
+
+        fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+            $(
+                if self.items.$n.is_none() {
+                    if self.futs.$n.is_none() {
+                        self.futs.$n = Some($T::from_request(&self.req, &self.cfg.$n));
+                    }
+                    match self.futs.$n.as_mut().unwrap().poll()? {
+                        Async::Ready(item) => {
+                            self.items.$n = Some(item);
+                            self.futs.$n.take();
+                        }
+                        Async::NotReady => return Ok(Async::NotReady),
+                    }
+                }
+            )+
+
+            Ok(Async::Ready(($(self.items.$n.take().unwrap(),)+)))
+        }
+    }
+});
+
+tuple_from_req!(TupleFromRequest1, (0, A));
+tuple_from_req!(TupleFromRequest2, (0, A), (1, B));
+tuple_from_req!(TupleFromRequest3, (0, A), (1, B), (2, C));
+tuple_from_req!(TupleFromRequest4, (0, A), (1, B), (2, C), (3, D));
+tuple_from_req!(TupleFromRequest5, (0, A), (1, B), (2, C), (3, D), (4, E));
+tuple_from_req!(
+    TupleFromRequest6,
+    (0, A),
+    (1, B),
+    (2, C),
+    (3, D),
+    (4, E),
+    (5, F)
+);
+tuple_from_req!(
+    TupleFromRequest7,
+    (0, A),
+    (1, B),
+    (2, C),
+    (3, D),
+    (4, E),
+    (5, F),
+    (6, G)
+);
+tuple_from_req!(
+    TupleFromRequest8,
+    (0, A),
+    (1, B),
+    (2, C),
+    (3, D),
+    (4, E),
+    (5, F),
+    (6, G),
+    (7, H)
+);
+tuple_from_req!(
+    TupleFromRequest9,
+    (0, A),
+    (1, B),
+    (2, C),
+    (3, D),
+    (4, E),
+    (5, F),
+    (6, G),
+    (7, H),
+    (8, I)
+);
+tuple_from_req!(
+    TupleFromRequest10,
+    (0, A),
+    (1, B),
+    (2, C),
+    (3, D),
+    (4, E),
+    (5, F),
+    (6, G),
+    (7, H),
+    (8, I),
+    (9, J)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use error::InternalError;
+    use httpresponse::HttpResponse;
+    use futures::future::lazy;
+    use futures::{Async, Future};
+    use http::{header, StatusCode};
+    use mime;
+    use tokio_core::reactor::Core;
+    use param::PathTail;
+    use resource::ResourceHandler;
+    use router::{Resource, Router};
+    use server::ServerSettings;
+    use test::TestRequest;
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Info {
+        hello: String,
+    }
+
+    #[test]
+    fn test_bytes() {
+        let cfg = PayloadConfig::default();
+        let mut req = TestRequest::with_header(header::CONTENT_LENGTH, "11").finish();
+        req.payload_mut()
+            .unread_data(Bytes::from_static(b"hello=world"));
+
+        match Bytes::from_request(&req, &cfg).poll().unwrap() {
+            Async::Ready(s) => {
+                assert_eq!(s, Bytes::from_static(b"hello=world"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_string() {
         let cfg = PayloadConfig::default();
         let mut req = TestRequest::with_header(header::CONTENT_LENGTH, "11").finish();
         req.payload_mut()
@@ -521,6 +1521,102 @@ mod tests {
         }
     }
 
+    #[derive(Deserialize, Debug)]
+    struct Upload {
+        title: String,
+    }
+
+    fn multipart_request(body: &'static [u8]) -> HttpRequest {
+        let mut req = TestRequest::with_header(
+            header::CONTENT_TYPE,
+            "multipart/form-data; boundary=abbc761f78ff4d7cb7573b5a23f96ef0",
+        ).header(header::CONTENT_LENGTH, body.len().to_string().as_str())
+            .finish();
+        req.payload_mut().unread_data(Bytes::from_static(body));
+        req
+    }
+
+    #[test]
+    fn test_multipart_form() {
+        let req = multipart_request(
+            b"--abbc761f78ff4d7cb7573b5a23f96ef0\r\n\
+              Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+              test\r\n\
+              --abbc761f78ff4d7cb7573b5a23f96ef0\r\n\
+              Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\
+              Content-Type: text/plain\r\n\r\n\
+              data\r\n\
+              --abbc761f78ff4d7cb7573b5a23f96ef0--\r\n",
+        );
+
+        let mut cfg = MultipartFormConfig::default();
+        cfg.memory_threshold(2);
+        let form = Core::new()
+            .unwrap()
+            .run(lazy(|| MultipartForm::<Upload>::from_request(&req, &cfg)))
+            .unwrap();
+        assert_eq!(form.title, "test");
+        let file = form.file("file").unwrap();
+        assert_eq!(file.file_name(), Some("a.txt"));
+        assert_eq!(file.content_type(), &mime::TEXT_PLAIN);
+        assert_eq!(file.size(), 4);
+        assert!(file.bytes().is_none());
+        let path = file.path().unwrap().to_owned();
+        assert_eq!(file.read().unwrap(), Bytes::from_static(b"data"));
+
+        drop(form);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_multipart_form_limits() {
+        let body = b"--abbc761f78ff4d7cb7573b5a23f96ef0\r\n\
+              Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+              test\r\n\
+              --abbc761f78ff4d7cb7573b5a23f96ef0--\r\n";
+
+        let req = multipart_request(body);
+        let mut cfg = MultipartFormConfig::default();
+        cfg.field_limit(2);
+        let err = Core::new()
+            .unwrap()
+            .run(lazy(|| MultipartForm::<Upload>::from_request(&req, &cfg)))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.cause().error_response().status(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+
+        let req = multipart_request(body);
+        let mut cfg = MultipartFormConfig::default();
+        cfg.max_fields(0);
+        let err = Core::new()
+            .unwrap()
+            .run(lazy(|| MultipartForm::<Upload>::from_request(&req, &cfg)))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.cause().error_response().status(),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+
+        let req = multipart_request(body);
+        let mut cfg = MultipartFormConfig::default();
+        cfg.max_fields(0).error_handler(|err, _| {
+            InternalError::from_response(err, HttpResponse::Conflict().finish()).into()
+        });
+        let err = Core::new()
+            .unwrap()
+            .run(lazy(|| MultipartForm::<Upload>::from_request(&req, &cfg)))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.cause().error_response().status(),
+            StatusCode::CONFLICT
+        );
+    }
+
     #[test]
     fn test_payload_config() {
         let req = HttpRequest::default();
@@ -570,7 +1666,7 @@ mod tests {
         let (router, _) = Router::new("", ServerSettings::default(), routes);
         assert!(router.recognize(&mut req).is_some());
 
-        match Path::<MyStruct>::from_request(&req, &())
+        match Path::<MyStruct>::from_request(&req, &PathConfig::default())
             .poll()
             .unwrap()
         {
@@ -581,7 +1677,7 @@ mod tests {
             _ => unreachable!(),
         }
 
-        match Path::<(String, String)>::from_request(&req, &())
+        match Path::<(String, String)>::from_request(&req, &PathConfig::default())
             .poll()
             .unwrap()
         {
@@ -592,7 +1688,7 @@ mod tests {
             _ => unreachable!(),
         }
 
-        match Query::<Id>::from_request(&req, &()).poll().unwrap() {
+        match Query::<Id>::from_request(&req, &QueryConfig::default()).poll().unwrap() {
             Async::Ready(s) => {
                 assert_eq!(s.id, "test");
             }
@@ -602,7 +1698,10 @@ mod tests {
         let mut req = TestRequest::with_uri("/name/32/").finish();
         assert!(router.recognize(&mut req).is_some());
 
-        match Path::<Test2>::from_request(&req, &()).poll().unwrap() {
+        match Path::<Test2>::from_request(&req, &PathConfig::default())
+            .poll()
+            .unwrap()
+        {
             Async::Ready(s) => {
                 assert_eq!(s.as_ref().key, "name");
                 assert_eq!(s.value, 32);
@@ -610,7 +1709,7 @@ mod tests {
             _ => unreachable!(),
         }
 
-        match Path::<(String, u8)>::from_request(&req, &())
+        match Path::<(String, u8)>::from_request(&req, &PathConfig::default())
             .poll()
             .unwrap()
         {
@@ -621,7 +1720,7 @@ mod tests {
             _ => unreachable!(),
         }
 
-        match Path::<Vec<String>>::from_request(&req, &())
+        match Path::<Vec<String>>::from_request(&req, &PathConfig::default())
             .poll()
             .unwrap()
         {
@@ -646,11 +1745,333 @@ mod tests {
         let mut req = TestRequest::with_uri("/32/").finish();
         assert!(router.recognize(&mut req).is_some());
 
-        match Path::<i8>::from_request(&req, &()).poll().unwrap() {
+        match Path::<i8>::from_request(&req, &PathConfig::default()).poll().unwrap() {
             Async::Ready(s) => {
                 assert_eq!(s.into_inner(), 32);
             }
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_extract_path_tail() {
+        #[derive(Deserialize)]
+        struct Archive {
+            year: String,
+            month: Option<String>,
+        }
+
+        let mut routes = Vec::new();
+        routes.push((
+            Resource::new("static", "/static/{tail}*"),
+            Some(ResourceHandler::<()>::default()),
+        ));
+        routes.push((
+            Resource::new("archive", "/archive/{year}/{month}?"),
+            Some(ResourceHandler::<()>::default()),
+        ));
+        let (router, _) = Router::new("", ServerSettings::default(), routes);
+
+        let mut req = TestRequest::with_uri("/static/css/../js//main.js").finish();
+        assert!(router.recognize(&mut req).is_some());
+        match Path::<PathTail>::from_request(&req, &PathConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(s) => {
+                assert_eq!(s.into_inner().into_inner(), PathBuf::from("js/main.js"));
+            }
+            _ => unreachable!(),
+        }
+        match Path::<String>::from_request(&req, &PathConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(s) => {
+                assert_eq!(s.into_inner(), "css/../js//main.js");
+            }
+            _ => unreachable!(),
+        }
+
+        let mut req = TestRequest::with_uri("/static/css/.hidden").finish();
+        assert!(router.recognize(&mut req).is_some());
+        assert!(
+            Path::<(PathTail,)>::from_request(&req, &PathConfig::default())
+                .poll()
+                .is_err()
+        );
+
+        let mut req = TestRequest::with_uri("/archive/2018").finish();
+        assert!(router.recognize(&mut req).is_some());
+        match Path::<Archive>::from_request(&req, &PathConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(s) => {
+                assert_eq!(s.year, "2018");
+                assert_eq!(s.month, None);
+            }
+            _ => unreachable!(),
+        }
+
+        let mut req = TestRequest::with_uri("/archive/2018/04").finish();
+        assert!(router.recognize(&mut req).is_some());
+        match Path::<Archive>::from_request(&req, &PathConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(s) => {
+                assert_eq!(s.year, "2018");
+                assert_eq!(s.month, Some("04".to_owned()));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_tuple_extract() {
+        let mut resource = ResourceHandler::<()>::default();
+        resource.name("index");
+        let mut routes = Vec::new();
+        routes.push((
+            Resource::new("index", "/{key}/{value}/"),
+            Some(resource),
+        ));
+        let (router, _) = Router::new("", ServerSettings::default(), routes);
+
+        let mut req = TestRequest::with_uri("/name/user1/?id=test")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .header(header::CONTENT_LENGTH, "11")
+            .finish();
+        req.payload_mut()
+            .unread_data(Bytes::from_static(b"hello=world"));
+        assert!(router.recognize(&mut req).is_some());
+
+        let mut cfg = <(
+            Path<MyStruct>,
+            Query<Id>,
+            HttpRequest,
+            Form<Info>,
+        ) as FromRequest<()>>::Config::default();
+        cfg.3.limit(4096);
+
+        match <(Path<MyStruct>, Query<Id>, HttpRequest, Form<Info>)>::from_request(
+            &req,
+            &cfg,
+        ).poll()
+            .unwrap()
+        {
+            Async::Ready((path, query, _, form)) => {
+                assert_eq!(path.key, "name");
+                assert_eq!(path.value, "user1");
+                assert_eq!(query.id, "test");
+                assert_eq!(form.hello, "world");
+            }
+            _ => unreachable!(),
+        }
+
+        let req = TestRequest::with_uri("/name/user1/").finish();
+        assert!(
+            <(HttpRequest, Query<Id>)>::from_request(&req, &Default::default())
+                .poll()
+                .is_err()
+        );
+
+        // extractors after failed one are not started
+        struct NotStarted;
+
+        impl FromRequest<()> for NotStarted {
+            type Config = ();
+            type Result = FutureResult<Self, Error>;
+
+            fn from_request(_: &HttpRequest<()>, _: &Self::Config) -> Self::Result {
+                panic!("extractor should not be started")
+            }
+        }
+
+        assert!(
+            <(Query<Id>, NotStarted)>::from_request(&req, &Default::default())
+                .poll()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_option_and_result() {
+        let req = TestRequest::with_uri("/?id=test").finish();
+        match Option::<Query<Id>>::from_request(&req, &QueryConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(Some(s)) => assert_eq!(s.id, "test"),
+            _ => unreachable!(),
+        }
+        match Result::<Query<Id>, Error>::from_request(&req, &QueryConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(Ok(s)) => assert_eq!(s.id, "test"),
+            _ => unreachable!(),
+        }
+
+        let req = TestRequest::with_uri("/?name=test").finish();
+        let err = Option::<Query<Id>>::from_request(&req, &QueryConfig::default())
+            .poll()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.cause().error_response().status(),
+            StatusCode::BAD_REQUEST
+        );
+        match Result::<Query<Id>, Error>::from_request(&req, &QueryConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(Err(err)) => assert_eq!(
+                err.cause().error_response().status(),
+                StatusCode::BAD_REQUEST
+            ),
+            _ => unreachable!(),
+        }
+
+        // absent query
+        let req = TestRequest::with_uri("/").finish();
+        match Option::<Query<Id>>::from_request(&req, &QueryConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(None) => (),
+            _ => unreachable!(),
+        }
+
+        // absent body
+        let req = TestRequest::default().finish();
+        match Option::<Form<Info>>::from_request(&req, &FormConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(None) => (),
+            _ => unreachable!(),
+        }
+
+        let mut req = TestRequest::with_header(
+            header::CONTENT_TYPE,
+            "application/x-www-form-urlencoded",
+        ).header(header::CONTENT_LENGTH, "11")
+            .finish();
+        req.payload_mut()
+            .unread_data(Bytes::from_static(b"hello=world"));
+        match Option::<Form<Info>>::from_request(&req, &FormConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(Some(form)) => assert_eq!(form.hello, "world"),
+            _ => unreachable!(),
+        }
+
+        // payload errors are not absorbed
+        let mut req = TestRequest::with_header(header::CONTENT_LENGTH, "11").finish();
+        req.payload_mut()
+            .unread_data(Bytes::from_static(b"hello=world"));
+        let mut cfg = PayloadConfig::default();
+        cfg.limit(5);
+        assert!(Option::<Bytes>::from_request(&req, &cfg).poll().is_err());
+    }
+
+    #[test]
+    fn test_header() {
+        use error::ErrorUnauthorized;
+        use header::{ContentType, IfModifiedSince};
+
+        let req = TestRequest::with_header(header::CONTENT_TYPE, "text/plain").finish();
+        let cfg = HeaderConfig::default();
+        match Header::<ContentType>::from_request(&req, &cfg)
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(ct) => assert_eq!(*ct, ContentType(mime::TEXT_PLAIN)),
+            _ => unreachable!(),
+        }
+        match Option::<Header<IfModifiedSince>>::from_request(&req, &cfg)
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(None) => (),
+            _ => unreachable!(),
+        }
+
+        // missing
+        let err = Header::<IfModifiedSince>::from_request(&req, &cfg)
+            .poll()
+            .err()
+            .unwrap();
+        let resp = err.cause().error_response();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(format!("{}", err), "Header if-modified-since is missing");
+
+        let mut cfg = HeaderConfig::default();
+        cfg.error_handler(|err, _| match err {
+            HeaderError::Missing(_) => ErrorUnauthorized("unauthorized"),
+            err => err.into(),
+        });
+        let err = Header::<IfModifiedSince>::from_request(&req, &cfg)
+            .poll()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.cause().error_response().status(),
+            StatusCode::UNAUTHORIZED
+        );
+
+        // malformed
+        let req =
+            TestRequest::with_header(header::IF_MODIFIED_SINCE, "yesterday").finish();
+        let err = Header::<IfModifiedSince>::from_request(&req, &cfg)
+            .poll()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.cause().error_response().status(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(format!("{}", err), "Header if-modified-since is malformed");
+
+        // malformed optional header is rejected
+        let err = Option::<Header<IfModifiedSince>>::from_request(&req, &cfg)
+            .poll()
+            .err()
+            .unwrap();
+        assert_eq!(format!("{}", err), "Header if-modified-since is malformed");
+    }
+
+    #[test]
+    fn test_error_handler() {
+        let req = TestRequest::with_uri("/?name=test").finish();
+
+        let mut cfg = QueryConfig::default();
+        cfg.error_handler(|err, _| {
+            InternalError::from_response(err, HttpResponse::Conflict().finish()).into()
+        });
+        let err = Query::<Id>::from_request(&req, &cfg)
+            .poll()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.cause().error_response().status(),
+            StatusCode::CONFLICT
+        );
+
+        let mut req = TestRequest::with_header(header::CONTENT_LENGTH, "11").finish();
+        req.payload_mut()
+            .unread_data(Bytes::from_static(b"hello=world"));
+        let mut cfg = PayloadConfig::default();
+        cfg.limit(5).error_handler(|err, _| {
+            InternalError::from_response(err, HttpResponse::Conflict().finish()).into()
+        });
+        let err = String::from_request(&req, &cfg).poll().err().unwrap();
+        assert_eq!(
+            err.cause().error_response().status(),
+            StatusCode::CONFLICT
+        );
+    }
 }
@@ -14,17 +14,20 @@ use std::os::unix::fs::MetadataExt;
 use bytes::{BufMut, Bytes, BytesMut};
 use futures::{Async, Future, Poll, Stream};
 use futures_cpupool::{CpuFuture, CpuPool};
+use mime::{self, Mime};
 use mime_guess::get_mime_type;
-use percent_encoding::percent_decode;
+use percent_encoding::{percent_decode, utf8_percent_encode, DEFAULT_ENCODE_SET};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json;
 
-use error::Error;
+use body::Body;
+use error::{Error, StaticFileError};
 use handler::{Handler, Reply, Responder, RouteHandler, WrapHandler};
 use header;
 use http::{Method, StatusCode};
 use httpmessage::HttpMessage;
 use httprequest::HttpRequest;
 use httpresponse::HttpResponse;
-use param::FromParam;
 
 /// Env variable for default cpu pool size for `StaticFiles`
 const ENV_CPU_POOL_VAR: &str = "ACTIX_FS_POOL";
@@ -215,7 +218,7 @@ impl Responder for NamedFile {
                 file: Some(self.file),
                 fut: None,
             };
-            return Ok(resp.streaming(reader));
+            return Ok(resp.body(Body::File(reader)));
         }
 
         if self.only_get && *req.method() != Method::GET && *req.method() != Method::HEAD
@@ -281,7 +284,7 @@ impl Responder for NamedFile {
                 file: Some(self.file),
                 fut: None,
             };
-            Ok(resp.streaming(reader))
+            Ok(resp.body(Body::File(reader)))
         }
     }
 }
@@ -296,6 +299,43 @@ pub struct ChunkedReadFile {
     fut: Option<CpuFuture<(File, Bytes), io::Error>>,
 }
 
+impl ChunkedReadFile {
+    /// Create reader for `size` bytes of the file, default `CpuPool` is used
+    pub(crate) fn new(file: File, size: u64) -> ChunkedReadFile {
+        ChunkedReadFile {
+            size,
+            offset: 0,
+            cpu_pool: DEFAULT_CPUPOOL.lock().unwrap().clone(),
+            file: Some(file),
+            fut: None,
+        }
+    }
+
+    /// Number of bytes that are not sent yet
+    #[inline]
+    pub(crate) fn remaining(&self) -> u64 {
+        self.size - self.offset
+    }
+
+    /// Current read position
+    #[inline]
+    pub(crate) fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Underlying file, available only while no chunk read is in progress
+    #[inline]
+    pub(crate) fn file(&self) -> &File {
+        self.file.as_ref().expect("Use after completion")
+    }
+
+    /// Mark `n` bytes as sent by the writer
+    #[inline]
+    pub(crate) fn advance(&mut self, n: u64) {
+        self.offset += n;
+    }
+}
+
 impl Stream for ChunkedReadFile {
     type Item = Bytes;
     type Error = Error;
@@ -341,23 +381,95 @@ impl Stream for ChunkedReadFile {
 pub struct Directory {
     base: PathBuf,
     path: PathBuf,
+    show_hidden: bool,
+    symlinks: SymlinkPolicy,
 }
 
 impl Directory {
     pub fn new(base: PathBuf, path: PathBuf) -> Directory {
-        Directory { base, path }
+        Directory {
+            base,
+            path,
+            show_hidden: false,
+            symlinks: SymlinkPolicy::WithinRoot,
+        }
+    }
+
+    /// Set symlinks policy, see `StaticFiles::symlinks()`.
+    pub fn symlinks(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlinks = policy;
+        self
+    }
+
+    /// Include hidden files (dotfiles) into listing.
+    ///
+    /// By default hidden files are not listed.
+    pub fn show_hidden(mut self) -> Self {
+        self.show_hidden = true;
+        self
+    }
+
+    /// Returns path of the listed directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read directory entries.
+    ///
+    /// Entry urls are relative to the current request path. Symlinks
+    /// are listed according to symlinks policy.
+    pub fn entries<S>(&self, req: &HttpRequest<S>) -> io::Result<Vec<DirectoryEntry>> {
+        let base = Path::new(req.path());
+        let mut entries = Vec::new();
+
+        for entry in self.path.read_dir()? {
+            if !self.can_list(&entry) {
+                continue;
+            }
+            let entry = entry.unwrap();
+            let p = match entry.path().strip_prefix(&self.path) {
+                Ok(p) => base.join(p),
+                Err(_) => continue,
+            };
+            // symlinks are resolved
+            let md = match entry.path().metadata() {
+                Ok(md) => md,
+                Err(_) => continue,
+            };
+
+            entries.push(DirectoryEntry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                url: utf8_percent_encode(&p.to_string_lossy(), DEFAULT_ENCODE_SET)
+                    .to_string(),
+                is_dir: md.is_dir(),
+                size: if md.is_dir() { 0 } else { md.len() },
+                modified: md.modified().ok(),
+            });
+        }
+        Ok(entries)
     }
 
     fn can_list(&self, entry: &io::Result<DirEntry>) -> bool {
         if let Ok(ref entry) = *entry {
             if let Some(name) = entry.file_name().to_str() {
-                if name.starts_with('.') {
+                if !self.show_hidden && name.starts_with('.') {
                     return false;
                 }
             }
             if let Ok(ref md) = entry.metadata() {
                 let ft = md.file_type();
-                return ft.is_dir() || ft.is_file() || ft.is_symlink();
+                if ft.is_symlink() {
+                    return match self.symlinks {
+                        SymlinkPolicy::Follow => true,
+                        SymlinkPolicy::Deny => false,
+                        // do not expose files outside of the base directory
+                        SymlinkPolicy::WithinRoot => match entry.path().canonicalize() {
+                            Ok(path) => path.starts_with(&self.base),
+                            Err(_) => false,
+                        },
+                    };
+                }
+                return ft.is_dir() || ft.is_file();
             }
         }
         false
@@ -369,40 +481,160 @@ impl Responder for Directory {
     type Error = io::Error;
 
     fn respond_to(self, req: HttpRequest) -> Result<HttpResponse, io::Error> {
-        let index_of = format!("Index of {}", req.path());
-        let mut body = String::new();
-        let base = Path::new(req.path());
+        HtmlRenderer::new().render(&self, &req)
+    }
+}
 
-        for entry in self.path.read_dir()? {
-            if self.can_list(&entry) {
-                let entry = entry.unwrap();
-                let p = match entry.path().strip_prefix(&self.path) {
-                    Ok(p) => base.join(p),
-                    Err(_) => continue,
-                };
-                // show file url as relative to static path
-                let file_url = format!("{}", p.to_string_lossy());
-
-                // if file is a directory, add '/' to the end of the name
-                if let Ok(metadata) = entry.metadata() {
-                    if metadata.is_dir() {
-                        let _ = write!(
-                            body,
-                            "<li><a href=\"{}\">{}/</a></li>",
-                            file_url,
-                            entry.file_name().to_string_lossy()
-                        );
-                    } else {
-                        let _ = write!(
-                            body,
-                            "<li><a href=\"{}\">{}</a></li>",
-                            file_url,
-                            entry.file_name().to_string_lossy()
-                        );
-                    }
-                } else {
-                    continue;
-                }
+/// Files listing entry
+#[derive(Debug, Clone)]
+pub struct DirectoryEntry {
+    /// File name
+    pub name: String,
+    /// Percent encoded url of the entry
+    pub url: String,
+    /// Entry is a directory
+    pub is_dir: bool,
+    /// File size in bytes, `0` for directories
+    pub size: u64,
+    /// Last modification time
+    pub modified: Option<SystemTime>,
+}
+
+impl Serialize for DirectoryEntry {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let modified = self.modified
+            .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+
+        let mut s = serializer.serialize_struct("DirectoryEntry", 5)?;
+        s.serialize_field("name", &self.name)?;
+        s.serialize_field("url", &self.url)?;
+        s.serialize_field("is_dir", &self.is_dir)?;
+        s.serialize_field("size", &self.size)?;
+        s.serialize_field("modified", &modified)?;
+        s.end()
+    }
+}
+
+/// Files listing renderer
+///
+/// `StaticFiles` selects renderer by request's `Accept` header.
+pub trait DirectoryRenderer: 'static {
+    /// Media type of generated listing
+    fn mime_type(&self) -> Mime;
+
+    /// Generate response for directory
+    fn render(&self, dir: &Directory, req: &HttpRequest) -> io::Result<HttpResponse>;
+}
+
+/// Sort order of files listing. Directories are always listed first.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DirectorySort {
+    /// Sort by file name
+    Name,
+    /// Sort by file size
+    Size,
+    /// Sort by last modification time
+    Modified,
+}
+
+fn sort_entries(entries: &mut [DirectoryEntry], sort: DirectorySort, descending: bool) {
+    entries.sort_by(|a, b| {
+        let ord = match sort {
+            DirectorySort::Name => a.name.cmp(&b.name),
+            DirectorySort::Size => a.size.cmp(&b.size),
+            DirectorySort::Modified => a.modified.cmp(&b.modified),
+        };
+        let ord = if descending { ord.reverse() } else { ord };
+        b.is_dir.cmp(&a.is_dir).then(ord)
+    });
+}
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Html files listing, shows file sizes and modification times.
+#[derive(Debug, Clone)]
+pub struct HtmlRenderer {
+    sort: DirectorySort,
+    descending: bool,
+}
+
+impl Default for HtmlRenderer {
+    fn default() -> HtmlRenderer {
+        HtmlRenderer {
+            sort: DirectorySort::Name,
+            descending: false,
+        }
+    }
+}
+
+impl HtmlRenderer {
+    /// Create new html renderer, entries are sorted by name.
+    pub fn new() -> HtmlRenderer {
+        HtmlRenderer::default()
+    }
+
+    /// Set sort order of entries
+    pub fn sort(mut self, sort: DirectorySort) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    /// Reverse sort order
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
+}
+
+impl DirectoryRenderer for HtmlRenderer {
+    fn mime_type(&self) -> Mime {
+        mime::TEXT_HTML
+    }
+
+    fn render(&self, dir: &Directory, req: &HttpRequest) -> io::Result<HttpResponse> {
+        let index_of = format!("Index of {}", escape_html(req.path()));
+        let mut entries = dir.entries(req)?;
+        sort_entries(&mut entries, self.sort, self.descending);
+
+        let mut body = String::new();
+        for entry in &entries {
+            let modified = entry
+                .modified
+                .map(|m| header::HttpDate::from(m).to_string())
+                .unwrap_or_default();
+
+            // if file is a directory, add '/' to the end of the name
+            if entry.is_dir {
+                let _ = write!(
+                    body,
+                    "<tr><td><a href=\"{}\">{}/</a></td><td>-</td><td>{}</td></tr>",
+                    entry.url,
+                    escape_html(&entry.name),
+                    modified
+                );
+            } else {
+                let _ = write!(
+                    body,
+                    "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td></tr>",
+                    entry.url,
+                    escape_html(&entry.name),
+                    entry.size,
+                    modified
+                );
             }
         }
 
@@ -410,9 +642,10 @@ impl Responder for Directory {
             "<html>\
              <head><title>{}</title></head>\
              <body><h1>{}</h1>\
-             <ul>\
+             <table>\
+             <tr><th>Name</th><th>Size</th><th>Modified</th></tr>\
              {}\
-             </ul></body>\n</html>",
+             </table></body>\n</html>",
             index_of, index_of, body
         );
         Ok(HttpResponse::Ok()
@@ -421,6 +654,38 @@ impl Responder for Directory {
     }
 }
 
+/// Json files listing, an array of `DirectoryEntry` objects.
+#[derive(Debug, Clone, Default)]
+pub struct JsonRenderer;
+
+impl DirectoryRenderer for JsonRenderer {
+    fn mime_type(&self) -> Mime {
+        mime::APPLICATION_JSON
+    }
+
+    fn render(&self, dir: &Directory, req: &HttpRequest) -> io::Result<HttpResponse> {
+        let mut entries = dir.entries(req)?;
+        sort_entries(&mut entries, DirectorySort::Name, false);
+
+        let body = serde_json::to_string(&entries)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        Ok(HttpResponse::Ok()
+            .content_type("application/json")
+            .body(body))
+    }
+}
+
+/// Symlinks handling policy of `StaticFiles`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SymlinkPolicy {
+    /// Follow all symlinks
+    Follow,
+    /// Follow symlinks that resolve to a path inside of the root directory
+    WithinRoot,
+    /// Do not serve files through symlinks
+    Deny,
+}
+
 /// Static files handling
 ///
 /// `StaticFile` handler must be registered with `App::handler()` method,
@@ -441,10 +706,13 @@ pub struct StaticFiles<S> {
     accessible: bool,
     index: Option<String>,
     show_index: bool,
+    show_hidden: bool,
+    renderers: Vec<Box<DirectoryRenderer>>,
     cpu_pool: CpuPool,
     default: Box<RouteHandler<S>>,
+    symlinks: SymlinkPolicy,
+    extensions: Option<Vec<String>>,
     _chunk_size: usize,
-    _follow_symlinks: bool,
 }
 
 lazy_static! {
@@ -501,23 +769,186 @@ impl<S: 'static> StaticFiles<S> {
             accessible: access,
             index: None,
             show_index: false,
+            show_hidden: false,
+            renderers: vec![Box::new(HtmlRenderer::new()), Box::new(JsonRenderer)],
             cpu_pool: pool,
             default: Box::new(WrapHandler::new(|_| {
                 HttpResponse::new(StatusCode::NOT_FOUND)
             })),
+            symlinks: SymlinkPolicy::WithinRoot,
+            extensions: None,
             _chunk_size: 0,
-            _follow_symlinks: false,
         }
     }
 
     /// Show files listing for directories.
     ///
-    /// By default show files listing is disabled.
+    /// By default show files listing is disabled. Listing is rendered
+    /// as html or json, depending on request's `Accept` header.
     pub fn show_files_listing(mut self) -> Self {
         self.show_index = true;
         self
     }
 
+    /// Register files listing renderer.
+    ///
+    /// Renderer replaces already registered renderer with the same
+    /// media type. Renderer for a request is selected by `Accept` header,
+    /// the first registered renderer is used if nothing matches.
+    ///
+    /// ```rust
+    /// # extern crate actix_web;
+    /// use actix_web::{fs, App};
+    ///
+    /// fn main() {
+    ///     let app = App::new()
+    ///         .handler(
+    ///             "/static",
+    ///             fs::StaticFiles::new(".")
+    ///                 .show_files_listing()
+    ///                 .files_listing_renderer(
+    ///                     fs::HtmlRenderer::new().sort(fs::DirectorySort::Modified)))
+    ///         .finish();
+    /// }
+    /// ```
+    pub fn files_listing_renderer<R: DirectoryRenderer>(mut self, renderer: R) -> Self {
+        let mt = renderer.mime_type();
+        if let Some(pos) = self.renderers.iter().position(|r| r.mime_type() == mt) {
+            self.renderers[pos] = Box::new(renderer);
+        } else {
+            self.renderers.push(Box::new(renderer));
+        }
+        self
+    }
+
+    /// Serve and list hidden files (dotfiles).
+    ///
+    /// By default requests for hidden files get refused with
+    /// `403 Forbidden` response and hidden files are not listed.
+    pub fn show_hidden_files(mut self) -> Self {
+        self.show_hidden = true;
+        self
+    }
+
+    /// Set symlinks handling policy.
+    ///
+    /// By default only symlinks that resolve to a path inside of the
+    /// root directory are followed. Requests for other symlinks get
+    /// refused with `403 Forbidden` response.
+    pub fn symlinks(mut self, policy: SymlinkPolicy) -> Self {
+        self.symlinks = policy;
+        self
+    }
+
+    /// Serve only files with specified extensions.
+    ///
+    /// Extensions are compared case-insensitively. Requests for other files
+    /// get refused with `403 Forbidden` response.
+    ///
+    /// ```rust
+    /// # extern crate actix_web;
+    /// use actix_web::{fs, App};
+    ///
+    /// fn main() {
+    ///     let app = App::new()
+    ///         .handler(
+    ///             "/static",
+    ///             fs::StaticFiles::new(".").allowed_extensions(vec!["html", "css", "js"]))
+    ///         .finish();
+    /// }
+    /// ```
+    pub fn allowed_extensions<I, T>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        self.extensions = Some(
+            extensions
+                .into_iter()
+                .map(|ext| ext.as_ref().trim_left_matches('.').to_lowercase())
+                .collect(),
+        );
+        self
+    }
+
+    /// Convert percent encoded request tail to a relative path.
+    fn relative_path(&self, tail: &str) -> Result<PathBuf, StaticFileError> {
+        let tail = percent_decode(tail.as_bytes())
+            .decode_utf8()
+            .map_err(|_| StaticFileError::InvalidPath)?;
+
+        let mut buf = PathBuf::new();
+        for segment in tail.split('/') {
+            if segment.is_empty() || segment == "." {
+                continue;
+            } else if segment == ".." {
+                return Err(StaticFileError::Forbidden);
+            } else if segment.starts_with('.') && !self.show_hidden {
+                return Err(StaticFileError::Forbidden);
+            } else if segment.starts_with('*') || segment.ends_with(':')
+                || segment.ends_with('>') || segment.ends_with('<')
+                || segment.contains('\0')
+                || (cfg!(windows) && segment.contains('\\'))
+            {
+                return Err(StaticFileError::InvalidPath);
+            }
+            buf.push(segment)
+        }
+        Ok(buf)
+    }
+
+    /// Check symlinks policy for resolved path.
+    fn check_symlinks(&self, relpath: &Path, path: &Path) -> Result<(), StaticFileError> {
+        match self.symlinks {
+            SymlinkPolicy::Follow => Ok(()),
+            SymlinkPolicy::WithinRoot => if path.starts_with(&self.directory) {
+                Ok(())
+            } else {
+                Err(StaticFileError::Forbidden)
+            },
+            SymlinkPolicy::Deny => {
+                let mut p = self.directory.clone();
+                for component in relpath.components() {
+                    p.push(component);
+                    if let Ok(md) = p.symlink_metadata() {
+                        if md.file_type().is_symlink() {
+                            return Err(StaticFileError::Forbidden);
+                        }
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Check file extension against allowed extensions.
+    fn check_extension(&self, path: &Path) -> Result<(), StaticFileError> {
+        if let Some(ref extensions) = self.extensions {
+            let allowed = path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| {
+                    let ext = ext.to_lowercase();
+                    extensions.iter().any(|e| *e == ext)
+                })
+                .unwrap_or(false);
+            if !allowed {
+                return Err(StaticFileError::Forbidden);
+            }
+        }
+        Ok(())
+    }
+
+    fn renderer(&self, req: &HttpRequest) -> &DirectoryRenderer {
+        let idx = req.get_header::<header::Accept>()
+            .and_then(|accept| {
+                let available: Vec<_> =
+                    self.renderers.iter().map(|r| r.mime_type()).collect();
+                accept.preferred(&available)
+            })
+            .unwrap_or(0);
+        self.renderers[idx].as_ref()
+    }
+
     /// Set index file
     ///
     /// Redirects to specific index file for directory "/" instead of
@@ -541,17 +972,14 @@ impl<S: 'static> Handler<S> for StaticFiles<S> {
         if !self.accessible {
             Ok(self.default.handle(req))
         } else {
-            let relpath = match req.match_info()
-                .get("tail")
-                .map(|tail| percent_decode(tail.as_bytes()).decode_utf8().unwrap())
-                .map(|tail| PathBuf::from_param(tail.as_ref()))
-            {
-                Some(Ok(path)) => path,
-                _ => return Ok(self.default.handle(req)),
+            let relpath = match req.match_info().get("tail") {
+                Some(tail) => self.relative_path(tail)?,
+                None => return Ok(self.default.handle(req)),
             };
 
             // full filepath
             let path = self.directory.join(&relpath).canonicalize()?;
+            self.check_symlinks(&relpath, &path)?;
 
             if path.is_dir() {
                 if let Some(ref redir_index) = self.index {
@@ -572,13 +1000,20 @@ impl<S: 'static> Handler<S> for StaticFiles<S> {
                         .finish()
                         .respond_to(req.drop_state())
                 } else if self.show_index {
-                    Directory::new(self.directory.clone(), path)
-                        .respond_to(req.drop_state())?
-                        .respond_to(req.drop_state())
+                    let mut dir =
+                        Directory::new(self.directory.clone(), path).symlinks(self.symlinks);
+                    if self.show_hidden {
+                        dir = dir.show_hidden();
+                    }
+                    let req = req.drop_state();
+                    self.renderer(&req)
+                        .render(&dir, &req)?
+                        .respond_to(req)
                 } else {
                     Ok(self.default.handle(req))
                 }
             } else {
+                self.check_extension(&path)?;
                 NamedFile::open(path)?
                     .set_cpu_pool(self.cpu_pool.clone())
                     .respond_to(req.drop_state())?
@@ -616,6 +1051,19 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_named_file_body() {
+        let file = NamedFile::open("Cargo.toml")
+            .unwrap()
+            .set_cpu_pool(CpuPool::new(1));
+        let resp = file.respond_to(HttpRequest::default()).unwrap();
+        let size = ::std::fs::metadata("Cargo.toml").unwrap().len();
+        match *resp.body() {
+            Body::File(ref reader) => assert_eq!(reader.remaining(), size),
+            _ => panic!("file body expected"),
+        }
+    }
+
     #[test]
     fn test_named_file_status_code() {
         let mut file = NamedFile::open("Cargo.toml")
@@ -689,6 +1137,186 @@ mod tests {
         assert!(format!("{:?}", resp.body()).contains("README.md"));
     }
 
+    fn listing_names(resp: &HttpResponse) -> Vec<String> {
+        let entries: Vec<serde_json::Value> = match *resp.body() {
+            Body::Binary(ref b) => serde_json::from_slice(b.as_ref()).unwrap(),
+            _ => panic!("binary body expected"),
+        };
+        entries
+            .iter()
+            .map(|e| e["name"].as_str().unwrap().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_files_listing_json() {
+        let mut st = StaticFiles::new(".").show_files_listing();
+        let mut req = TestRequest::with_header(header::ACCEPT, "application/json").finish();
+        req.match_info_mut().add("tail", "");
+
+        let resp = st.handle(req)
+            .respond_to(HttpRequest::default())
+            .unwrap();
+        let resp = resp.as_response().expect("HTTP Response");
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        let names = listing_names(resp);
+        assert!(names.contains(&"README.md".to_owned()));
+        assert!(names.contains(&"src".to_owned()));
+        assert!(!names.iter().any(|n| n.starts_with('.')));
+
+        let mut st = StaticFiles::new(".")
+            .show_files_listing()
+            .show_hidden_files();
+        let mut req = TestRequest::with_header(header::ACCEPT, "application/json").finish();
+        req.match_info_mut().add("tail", "");
+
+        let resp = st.handle(req)
+            .respond_to(HttpRequest::default())
+            .unwrap();
+        let names = listing_names(resp.as_response().unwrap());
+        assert!(names.iter().any(|n| n.starts_with('.')));
+    }
+
+    #[test]
+    fn test_files_listing_sort() {
+        let mut st = StaticFiles::new(".")
+            .show_files_listing()
+            .files_listing_renderer(HtmlRenderer::new().sort(DirectorySort::Size));
+        let mut req = TestRequest::with_header(header::ACCEPT, "text/html").finish();
+        req.match_info_mut().add("tail", "");
+
+        let resp = st.handle(req)
+            .respond_to(HttpRequest::default())
+            .unwrap();
+        let resp = resp.as_response().expect("HTTP Response");
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/html; charset=utf-8"
+        );
+        let body = format!("{:?}", resp.body());
+        // directories first
+        assert!(body.find("src/").unwrap() < body.find("Cargo.toml").unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_files_listing_symlink_escape() {
+        use std::os::unix::fs::symlink;
+
+        let root = env::temp_dir().join("actix-web-listing-test");
+        let _ = ::std::fs::remove_dir_all(&root);
+        ::std::fs::create_dir_all(&root).unwrap();
+        File::create(root.join("inner.txt")).unwrap();
+        symlink(root.join("inner.txt"), root.join("link-in")).unwrap();
+        symlink(env::temp_dir(), root.join("link-out")).unwrap();
+
+        let mut st = StaticFiles::new(&root).show_files_listing();
+        let mut req = TestRequest::with_header(header::ACCEPT, "application/json").finish();
+        req.match_info_mut().add("tail", "");
+
+        let resp = st.handle(req)
+            .respond_to(HttpRequest::default())
+            .unwrap();
+        let mut names = listing_names(resp.as_response().unwrap());
+        names.sort();
+        assert_eq!(names, vec!["inner.txt".to_owned(), "link-in".to_owned()]);
+
+        let _ = ::std::fs::remove_dir_all(&root);
+    }
+
+    fn static_files_status(st: &mut StaticFiles<()>, tail: &str) -> StatusCode {
+        let mut req = HttpRequest::default();
+        req.match_info_mut().add("tail", tail);
+
+        match st.handle(req) {
+            Ok(reply) => reply.as_response().expect("HTTP Response").status(),
+            Err(err) => err.cause().error_response().status(),
+        }
+    }
+
+    #[test]
+    fn test_static_files_traversal() {
+        let mut st = StaticFiles::new("src");
+        assert_eq!(static_files_status(&mut st, "fs.rs"), StatusCode::OK);
+        assert_eq!(static_files_status(&mut st, "../Cargo.toml"), StatusCode::FORBIDDEN);
+        assert_eq!(
+            static_files_status(&mut st, "%2e%2e/Cargo.toml"),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            static_files_status(&mut st, "%2E%2E%2FCargo.toml"),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            static_files_status(&mut st, "header/..%2f..%2fCargo.toml"),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(static_files_status(&mut st, "%ff"), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn test_static_files_hidden() {
+        let mut st = StaticFiles::new(".");
+        assert_eq!(static_files_status(&mut st, ".gitignore"), StatusCode::FORBIDDEN);
+        assert_eq!(
+            static_files_status(&mut st, "%2egitignore"),
+            StatusCode::FORBIDDEN
+        );
+
+        let mut st = StaticFiles::new(".").show_hidden_files();
+        assert_eq!(static_files_status(&mut st, ".gitignore"), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_static_files_extensions() {
+        let mut st = StaticFiles::new(".").allowed_extensions(vec!["md", ".TOML"]);
+        assert_eq!(static_files_status(&mut st, "README.md"), StatusCode::OK);
+        assert_eq!(static_files_status(&mut st, "Cargo.toml"), StatusCode::OK);
+        assert_eq!(static_files_status(&mut st, "build.rs"), StatusCode::FORBIDDEN);
+        assert_eq!(static_files_status(&mut st, "LICENSE-MIT"), StatusCode::FORBIDDEN);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_static_files_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let root = env::temp_dir().join("actix-web-symlinks-test");
+        let _ = ::std::fs::remove_dir_all(&root);
+        ::std::fs::create_dir_all(root.join("public")).unwrap();
+        File::create(root.join("secret.txt")).unwrap();
+        File::create(root.join("public/index.txt")).unwrap();
+        symlink(root.join("public/index.txt"), root.join("public/link-in")).unwrap();
+        symlink(root.join("secret.txt"), root.join("public/link-out")).unwrap();
+        symlink(&root, root.join("public/dir-out")).unwrap();
+
+        let mut st = StaticFiles::new(root.join("public"));
+        assert_eq!(static_files_status(&mut st, "index.txt"), StatusCode::OK);
+        assert_eq!(static_files_status(&mut st, "link-in"), StatusCode::OK);
+        assert_eq!(static_files_status(&mut st, "link-out"), StatusCode::FORBIDDEN);
+        assert_eq!(
+            static_files_status(&mut st, "dir-out/secret.txt"),
+            StatusCode::FORBIDDEN
+        );
+
+        let mut st = StaticFiles::new(root.join("public")).symlinks(SymlinkPolicy::Deny);
+        assert_eq!(static_files_status(&mut st, "index.txt"), StatusCode::OK);
+        assert_eq!(static_files_status(&mut st, "link-in"), StatusCode::FORBIDDEN);
+
+        let mut st =
+            StaticFiles::new(root.join("public")).symlinks(SymlinkPolicy::Follow);
+        assert_eq!(static_files_status(&mut st, "link-out"), StatusCode::OK);
+        assert_eq!(
+            static_files_status(&mut st, "dir-out/secret.txt"),
+            StatusCode::OK
+        );
+
+        let _ = ::std::fs::remove_dir_all(&root);
+    }
+
     #[test]
     fn test_redirect_to_index() {
         let mut st = StaticFiles::new(".").index_file("index.html");
@@ -809,4 +1437,29 @@ mod tests {
         let response = srv.execute(request.send()).unwrap();
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[test]
+    fn integration_named_file_sendfile() {
+        let mut srv = test::TestServer::with_factory(|| {
+            App::new().resource("/", |r| r.f(|_| NamedFile::open("Cargo.toml")))
+        });
+
+        // no `Accept-Encoding`, response is sent with `sendfile()` on linux
+        let request = srv.get().no_default_headers().finish().unwrap();
+        let response = srv.execute(request.send()).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = srv.execute(response.body()).unwrap();
+        let mut data = Vec::new();
+        File::open("Cargo.toml")
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        assert_eq!(bytes, Bytes::from(data));
+
+        // compressed response falls back to chunked read
+        let request = srv.get().disable_decompress().finish().unwrap();
+        let response = srv.execute(request.send()).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().contains_key(header::CONTENT_ENCODING));
+    }
 }
@@ -46,6 +46,16 @@ where
 
     /// Convert request to a Self
     fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result;
+
+    /// Check if the value is absent from the request, i.e. header is not set,
+    /// request has no body or query string is empty.
+    ///
+    /// `Option<T>` extractor resolves to `None` for absent values,
+    /// by default value is never absent.
+    #[inline]
+    fn is_absent(_req: &HttpRequest<S>, _cfg: &Self::Config) -> bool {
+        false
+    }
 }
 
 /// Combines two different responder types into a single type
@@ -1,4 +1,4 @@
-use header::{qitem, QualityItem};
+use header::{q, qitem, Quality, QualityItem};
 use http::header as http;
 use mime::{self, Mime};
 
@@ -126,6 +126,31 @@ header! {
                     q(500)),
             ])));
 
+        #[test]
+        fn test_preferred() {
+            let available = vec![TEXT_HTML, APPLICATION_JSON];
+
+            let accept = Accept(vec![
+                QualityItem::new(TEXT_HTML, q(500)),
+                qitem(APPLICATION_JSON),
+            ]);
+            assert_eq!(accept.preferred(&available), Some(1));
+
+            let accept = Accept(vec![
+                QualityItem::new(TEXT_STAR, q(800)),
+                QualityItem::new(STAR_STAR, q(100)),
+            ]);
+            assert_eq!(accept.preferred(&available), Some(0));
+
+            let accept = Accept(vec![
+                qitem(STAR_STAR),
+                QualityItem::new(APPLICATION_JSON, q(0)),
+            ]);
+            assert_eq!(accept.preferred(&available[1..]), None);
+            assert_eq!(Accept::star().preferred(&available), Some(0));
+            assert_eq!(Accept::image().preferred(&available), None);
+        }
+
         #[test]
         fn test_fuzzing1() {
             use test::TestRequest;
@@ -156,4 +181,40 @@ impl Accept {
     pub fn image() -> Accept {
         Accept(vec![qitem(mime::IMAGE_STAR)])
     }
+
+    /// Returns index of the most preferred media type from `available`.
+    ///
+    /// Quality of a media type is defined by the most specific matching
+    /// media range, ties are resolved in favor of the first type in
+    /// `available`. Returns `None` if no type is acceptable.
+    pub fn preferred(&self, available: &[Mime]) -> Option<usize> {
+        let mut best: Option<(usize, Quality)> = None;
+
+        for (idx, mt) in available.iter().enumerate() {
+            let mut matched: Option<(u8, Quality)> = None;
+            for item in &self.0 {
+                let specificity = if item.item.type_() == mime::STAR {
+                    0
+                } else if item.item.type_() != mt.type_() {
+                    continue;
+                } else if item.item.subtype() == mime::STAR {
+                    1
+                } else if item.item.subtype() == mt.subtype() {
+                    2
+                } else {
+                    continue;
+                };
+                if matched.map_or(true, |(s, _)| specificity > s) {
+                    matched = Some((specificity, item.quality));
+                }
+            }
+
+            if let Some((_, quality)) = matched {
+                if quality > q(0u16) && best.map_or(true, |(_, b)| quality > b) {
+                    best = Some((idx, quality));
+                }
+            }
+        }
+        best.map(|(idx, _)| idx)
+    }
 }
@@ -1,6 +1,8 @@
 //! Various helpers
 
-use http::{header, StatusCode};
+use std::cmp;
+
+use http::{header, uri, StatusCode, Uri};
 use regex::Regex;
 
 use handler::Handler;
@@ -168,10 +170,173 @@ impl<S> Handler<S> for NormalizePath {
     }
 }
 
+/// Trailing slash handling of `PathPolicy`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TrailingSlash {
+    /// Trailing slash is kept as is
+    Keep,
+    /// Trailing slash is removed, `/path/` becomes `/path`
+    Trim,
+    /// Trailing slash is added, `/path` becomes `/path/`
+    Append,
+}
+
+/// Application path normalization policy
+///
+/// Unlike `NormalizePath` handler, policy is applied to every request
+/// before route recognition. By default path is rewritten internally,
+/// with `PathPolicy::redirect()` client is redirected to normalized path.
+///
+/// Resource patterns are normalized with the same policy, so urls
+/// generated with `HttpRequest::url_for()` are already normalized.
+/// Paths of handlers registered with `App::handler()` are not changed.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::http::{PathPolicy, TrailingSlash};
+/// use actix_web::{App, HttpResponse};
+///
+/// fn main() {
+///     let app = App::new()
+///         .path_policy(
+///             PathPolicy::new()
+///                 .merge_slashes(true)
+///                 .trailing_slash(TrailingSlash::Trim),
+///         )
+///         .resource("/test", |r| r.f(|_| HttpResponse::Ok()))
+///         .finish();
+/// }
+/// ```
+/// In this example `/test/` and `//test//` are handled by `/test` resource.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PathPolicy {
+    merge: bool,
+    trailing: TrailingSlash,
+    redirect: Option<StatusCode>,
+}
+
+impl Default for PathPolicy {
+    /// Policy that does not change path
+    fn default() -> PathPolicy {
+        PathPolicy {
+            merge: false,
+            trailing: TrailingSlash::Keep,
+            redirect: None,
+        }
+    }
+}
+
+impl PathPolicy {
+    /// Create policy that does not change path
+    pub fn new() -> PathPolicy {
+        PathPolicy::default()
+    }
+
+    /// Merge multiple consecutive slashes into one
+    pub fn merge_slashes(mut self, merge: bool) -> Self {
+        self.merge = merge;
+        self
+    }
+
+    /// Set trailing slash handling
+    pub fn trailing_slash(mut self, trailing: TrailingSlash) -> Self {
+        self.trailing = trailing;
+        self
+    }
+
+    /// Redirect to normalized path instead of internal rewrite,
+    /// i.e. with `StatusCode::MOVED_PERMANENTLY`
+    pub fn redirect(mut self, status: StatusCode) -> Self {
+        self.redirect = Some(status);
+        self
+    }
+
+    /// Normalize path, returns `None` if path is already normalized
+    pub fn normalize(&self, path: &str) -> Option<String> {
+        let mut result = String::with_capacity(path.len() + 1);
+        for ch in path.chars() {
+            if !(self.merge && ch == '/' && result.ends_with('/')) {
+                result.push(ch);
+            }
+        }
+        match self.trailing {
+            TrailingSlash::Keep => (),
+            TrailingSlash::Trim => {
+                let len = result.trim_right_matches('/').len();
+                result.truncate(cmp::max(len, 1));
+            }
+            TrailingSlash::Append => if !result.ends_with('/') {
+                result.push('/');
+            },
+        }
+        if result == path {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    /// Normalize resource pattern, trailing slash is kept for patterns
+    /// that end with a tail or an optional parameter
+    pub(crate) fn normalize_pattern(&self, pattern: &str) -> Option<String> {
+        let tail = ["}*", "}?", ":.*}"];
+        if tail.iter().any(|end| pattern.ends_with(*end)) {
+            PathPolicy {
+                trailing: TrailingSlash::Keep,
+                ..*self
+            }.normalize(pattern)
+        } else {
+            self.normalize(pattern)
+        }
+    }
+
+    /// Apply policy to request's path, returns `true` if request
+    /// has to be redirected
+    pub(crate) fn apply<S>(&self, req: &mut HttpRequest<S>) -> bool {
+        let path = match self.normalize(req.path()) {
+            Some(path) => path,
+            None => return false,
+        };
+        if self.redirect.is_some() {
+            return true;
+        }
+
+        let path = match req.uri().query() {
+            Some(query) => path + "?" + query,
+            None => path,
+        };
+        let mut parts = uri::Parts::from(req.uri().clone());
+        parts.path_and_query = path.parse().ok();
+        if let Ok(uri) = Uri::from_parts(parts) {
+            *req.uri_mut() = uri;
+        }
+        false
+    }
+
+    /// Redirect to normalized path, leading slashes are always merged,
+    /// otherwise `//host` location is treated by clients as other host
+    pub(crate) fn redirect_response<S>(&self, req: &HttpRequest<S>) -> HttpResponse {
+        let path = self.normalize(req.path())
+            .unwrap_or_else(|| req.path().to_owned());
+        let path = format!(
+            "/{}",
+            path.trim_left_matches(|c: char| c == '/' || c == '\\')
+        );
+        let location = match req.uri().query() {
+            Some(query) => path + "?" + query,
+            None => path,
+        };
+        HttpResponse::build(self.redirect.unwrap_or(StatusCode::MOVED_PERMANENTLY))
+            .header(header::LOCATION, location.as_str())
+            .finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use application::App;
+    use fs::StaticFiles;
     use http::{header, Method};
     use test::TestRequest;
 
@@ -549,4 +714,204 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_path_policy_with_state() {
+        let mut app = App::with_state(10)
+            .path_policy(PathPolicy::new().trailing_slash(TrailingSlash::Trim))
+            .resource("/resource", |r| {
+                r.method(Method::GET).f(|req: HttpRequest<i32>| {
+                    assert_eq!(*req.state(), 10);
+                    HttpResponse::Ok()
+                })
+            })
+            .finish();
+
+        let req = app.prepare_request(TestRequest::with_uri("/resource/").finish());
+        let resp = app.run(req);
+        assert_eq!(resp.as_response().unwrap().status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_path_policy_redirect_other_host() {
+        let mut app = App::new()
+            .path_policy(
+                PathPolicy::new()
+                    .trailing_slash(TrailingSlash::Append)
+                    .redirect(StatusCode::MOVED_PERMANENTLY),
+            )
+            .resource("/resource1", |r| r.method(Method::GET).f(index))
+            .finish();
+
+        let req = app.prepare_request(TestRequest::with_uri("//evil.com").finish());
+        let resp = app.run(req);
+        let r = resp.as_response().unwrap();
+        assert_eq!(r.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(r.headers().get(header::LOCATION).unwrap(), "/evil.com/");
+    }
+
+    #[test]
+    fn test_path_policy_static_files() {
+        let mut app = App::new()
+            .path_policy(
+                PathPolicy::new()
+                    .trailing_slash(TrailingSlash::Append)
+                    .redirect(StatusCode::MOVED_PERMANENTLY),
+            )
+            .resource("/resource1", |r| r.method(Method::GET).f(index))
+            .handler("/static", StaticFiles::new("."))
+            .finish();
+
+        let req =
+            app.prepare_request(TestRequest::with_uri("/static/Cargo.toml").finish());
+        let resp = app.run(req);
+        assert_eq!(resp.as_response().unwrap().status(), StatusCode::OK);
+
+        let req = app.prepare_request(TestRequest::with_uri("/resource1").finish());
+        let resp = app.run(req);
+        assert_eq!(
+            resp.as_response().unwrap().status(),
+            StatusCode::MOVED_PERMANENTLY
+        );
+    }
+
+    #[test]
+    fn test_path_policy_normalize() {
+        let policy = PathPolicy::new();
+        assert_eq!(policy.normalize("//test//"), None);
+
+        let policy = PathPolicy::new().merge_slashes(true);
+        assert_eq!(policy.normalize("/test"), None);
+        assert_eq!(policy.normalize("//test///a//"), Some("/test/a/".to_owned()));
+
+        let policy = PathPolicy::new().trailing_slash(TrailingSlash::Trim);
+        assert_eq!(policy.normalize("/"), None);
+        assert_eq!(policy.normalize("/test"), None);
+        assert_eq!(policy.normalize("/test//"), Some("/test".to_owned()));
+        assert_eq!(policy.normalize("//"), Some("/".to_owned()));
+
+        let policy = PathPolicy::new()
+            .merge_slashes(true)
+            .trailing_slash(TrailingSlash::Append);
+        assert_eq!(policy.normalize("/"), None);
+        assert_eq!(policy.normalize("/test/"), None);
+        assert_eq!(policy.normalize("//test"), Some("/test/".to_owned()));
+
+        assert_eq!(policy.normalize_pattern("/static/{tail:.*}"), None);
+        assert_eq!(policy.normalize_pattern("/static/{tail}*"), None);
+        assert_eq!(
+            policy.normalize_pattern("//archive/{year}?"),
+            Some("/archive/{year}?".to_owned())
+        );
+        assert_eq!(
+            policy.normalize_pattern("/user/{name}"),
+            Some("/user/{name}/".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_path_policy_rewrite() {
+        let mut app = App::new()
+            .path_policy(
+                PathPolicy::new()
+                    .merge_slashes(true)
+                    .trailing_slash(TrailingSlash::Trim),
+            )
+            .resource("/resource1", |r| r.method(Method::GET).f(index))
+            .resource("/resource2/", |r| r.method(Method::GET).f(index))
+            .finish();
+
+        let params = vec![
+            ("/resource1", StatusCode::OK),
+            ("/resource1/", StatusCode::OK),
+            ("//resource1//", StatusCode::OK),
+            ("/resource1//?p=1", StatusCode::OK),
+            ("/resource2", StatusCode::OK),
+            ("/resource2/", StatusCode::OK),
+            ("/resource3/", StatusCode::NOT_FOUND),
+        ];
+        for (path, code) in params {
+            let req = app.prepare_request(TestRequest::with_uri(path).finish());
+            let resp = app.run(req);
+            let r = resp.as_response().unwrap();
+            assert_eq!(r.status(), code, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_path_policy_rewrite_query() {
+        let mut app = App::new()
+            .path_policy(PathPolicy::new().trailing_slash(TrailingSlash::Append))
+            .resource("/resource", |r| {
+                r.method(Method::GET).f(|req| {
+                    assert_eq!(req.path(), "/resource/");
+                    assert_eq!(req.query_string(), "p=1");
+                    HttpResponse::Ok()
+                })
+            })
+            .finish();
+
+        let req = app.prepare_request(TestRequest::with_uri("/resource?p=1").finish());
+        let resp = app.run(req);
+        assert_eq!(resp.as_response().unwrap().status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_path_policy_redirect() {
+        let mut app = App::new()
+            .path_policy(
+                PathPolicy::new()
+                    .merge_slashes(true)
+                    .trailing_slash(TrailingSlash::Append)
+                    .redirect(StatusCode::MOVED_PERMANENTLY),
+            )
+            .resource("/resource1", |r| r.method(Method::GET).f(index))
+            .finish();
+
+        let params = vec![
+            ("/resource1/", "", StatusCode::OK),
+            ("/resource1", "/resource1/", StatusCode::MOVED_PERMANENTLY),
+            (
+                "//resource1?p1=1&p2=2",
+                "/resource1/?p1=1&p2=2",
+                StatusCode::MOVED_PERMANENTLY,
+            ),
+            ("/resource2/", "", StatusCode::NOT_FOUND),
+        ];
+        for (path, target, code) in params {
+            let req = app.prepare_request(TestRequest::with_uri(path).finish());
+            let resp = app.run(req);
+            let r = resp.as_response().unwrap();
+            assert_eq!(r.status(), code);
+            if !target.is_empty() {
+                assert_eq!(
+                    target,
+                    r.headers()
+                        .get(header::LOCATION)
+                        .unwrap()
+                        .to_str()
+                        .unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_path_policy_url_for() {
+        let mut app = App::new()
+            .path_policy(PathPolicy::new().trailing_slash(TrailingSlash::Append))
+            .resource("/user/{name}", |r| {
+                r.name("user");
+                r.method(Method::GET).f(|req| {
+                    let url = req.url_for("user", &["test"]).unwrap();
+                    assert_eq!(url.path(), "/user/test/");
+                    HttpResponse::Ok()
+                })
+            })
+            .finish();
+
+        let req = app.prepare_request(TestRequest::with_uri("/user/test").finish());
+        let resp = app.run(req);
+        assert_eq!(resp.as_response().unwrap().status(), StatusCode::OK);
+    }
 }
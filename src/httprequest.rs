@@ -195,6 +195,16 @@ impl<S> HttpRequest<S> {
         self.1.as_ref().unwrap()
     }
 
+    /// Apply application's default extractor error handler
+    pub(crate) fn extractor_error(&self, err: Error) -> Error {
+        if let Some(ref router) = self.2 {
+            if let Some(handler) = router.error_handler() {
+                return (*handler)(err, self.drop_state());
+            }
+        }
+        err
+    }
+
     /// Request extensions
     #[inline]
     pub fn extensions(&mut self) -> &mut Extensions {
@@ -733,6 +743,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_url_for_static_tail() {
+        let mut resource = ResourceHandler::<()>::default();
+        resource.name("profile");
+        let mut resource2 = ResourceHandler::<()>::default();
+        resource2.name("index");
+        let routes = vec![
+            (Resource::new("profile", "/user/{name}/profile/"), Some(resource)),
+            (Resource::new("index", "/index.html"), Some(resource2)),
+        ];
+        let (router, _) = Router::new("/", ServerSettings::default(), routes);
+
+        let req = TestRequest::with_header(header::HOST, "www.rust-lang.org")
+            .finish_with_router(router);
+        let url = req.url_for("profile", &["test"]);
+        assert_eq!(
+            url.ok().unwrap().as_str(),
+            "http://www.rust-lang.org/user/test/profile/"
+        );
+        let url = req.url_for("index", &[] as &[&str]);
+        assert_eq!(
+            url.ok().unwrap().as_str(),
+            "http://www.rust-lang.org/index.html"
+        );
+    }
+
     #[test]
     fn test_url_for_external() {
         let req = HttpRequest::default();
@@ -1,7 +1,9 @@
 use bytes::{Bytes, BytesMut};
-use futures::{Future, Poll, Stream};
+use futures::future::{self, FutureResult};
+use futures::{Async, Future, Poll, Stream};
 use http::header::CONTENT_LENGTH;
 use std::fmt;
+use std::marker::PhantomData;
 use std::rc::Rc;
 use std::ops::{Deref, DerefMut};
 
@@ -11,11 +13,13 @@ use serde::de::DeserializeOwned;
 use serde_json;
 
 use error::{Error, JsonPayloadError, PayloadError};
+use extractor::payload_is_absent;
 use handler::{FromRequest, Responder};
 use http::StatusCode;
 use httpmessage::HttpMessage;
 use httprequest::HttpRequest;
 use httpresponse::HttpResponse;
+use payload::PayloadHelper;
 
 /// Json helper
 ///
@@ -146,6 +150,11 @@ where
                 .map(Json),
         )
     }
+
+    #[inline]
+    fn is_absent(req: &HttpRequest<S>, _: &Self::Config) -> bool {
+        payload_is_absent(req)
+    }
 }
 
 /// Json extractor configuration
@@ -203,7 +212,16 @@ impl<S> JsonConfig<S> {
 impl<S> Default for JsonConfig<S> {
     fn default() -> Self {
         JsonConfig { limit: 262_144,
-                     ehandler: Rc::new(|e, _| e.into()) }
+                     ehandler: Rc::new(|e, req| req.extractor_error(e.into())) }
+    }
+}
+
+impl<S> Clone for JsonConfig<S> {
+    fn clone(&self) -> Self {
+        JsonConfig {
+            limit: self.limit,
+            ehandler: Rc::clone(&self.ehandler),
+        }
     }
 }
 
@@ -313,9 +331,201 @@ where
     }
 }
 
+/// Newline delimited json stream
+///
+/// Request payload is split to lines and each line is deserialized to
+/// a `T` value as soon as it is received, empty lines are skipped. Stream
+/// could be used as an extractor, `JsonConfig::limit()` sets max size of
+/// a single line.
+///
+/// Content type has to be `application/x-ndjson` or `application/x-jsonlines`.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// # extern crate futures;
+/// #[macro_use] extern crate serde_derive;
+/// use actix_web::{http, App, Error, JsonStream};
+/// use futures::{Future, Stream};
+///
+/// #[derive(Deserialize)]
+/// struct Event {
+///     name: String,
+/// }
+///
+/// /// count events without buffering whole request body
+/// fn index(events: JsonStream<Event>) -> Box<Future<Item = String, Error = Error>> {
+///     Box::new(
+///         events
+///             .from_err()
+///             .fold(0, |count, _| Ok::<_, Error>(count + 1))
+///             .map(|count| format!("{} events", count)),
+///     )
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource("/events", |r| {
+///         r.method(http::Method::POST).with_async(index)
+///     });
+/// }
+/// ```
+pub struct JsonStream<T> {
+    limit: usize,
+    payload: PayloadHelper<HttpRequest>,
+    err: Option<JsonPayloadError>,
+    eof: bool,
+    _t: PhantomData<T>,
+}
+
+impl<T: DeserializeOwned> JsonStream<T> {
+    /// Create `JsonStream` for request.
+    pub fn new<S>(req: &HttpRequest<S>) -> Self {
+        let ndjson = if let Ok(Some(mime)) = req.mime_type() {
+            mime.type_() == mime::APPLICATION
+                && (mime.subtype() == "x-ndjson" || mime.subtype() == "x-jsonlines")
+        } else {
+            false
+        };
+
+        JsonStream {
+            limit: 262_144,
+            payload: PayloadHelper::new(req.drop_state()),
+            err: if ndjson {
+                None
+            } else {
+                Some(JsonPayloadError::ContentType)
+            },
+            eof: false,
+            _t: PhantomData,
+        }
+    }
+
+    /// Change max size of a single line. By default max size is 256Kb
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+}
+
+impl<T: DeserializeOwned> Stream for JsonStream<T> {
+    type Item = T;
+    type Error = JsonPayloadError;
+
+    fn poll(&mut self) -> Poll<Option<T>, JsonPayloadError> {
+        if let Some(err) = self.err.take() {
+            self.eof = true;
+            return Err(err);
+        }
+
+        loop {
+            if self.eof {
+                return Ok(Async::Ready(None));
+            }
+            let line = match self.payload.readline()? {
+                Async::Ready(Some(line)) => line,
+                Async::Ready(None) => {
+                    // last line without trailing new line
+                    self.eof = true;
+                    self.payload.remaining()
+                }
+                Async::NotReady => {
+                    if self.payload.len() > self.limit {
+                        self.eof = true;
+                        return Err(JsonPayloadError::Overflow);
+                    }
+                    return Ok(Async::NotReady);
+                }
+            };
+            if line.len() > self.limit {
+                self.eof = true;
+                return Err(JsonPayloadError::Overflow);
+            }
+
+            let empty = line.iter().all(|ch| match *ch {
+                b' ' | b'\t' | b'\r' | b'\n' => true,
+                _ => false,
+            });
+            if !empty {
+                return Ok(Async::Ready(Some(serde_json::from_slice(&line)?)));
+            }
+        }
+    }
+}
+
+impl<T, S> FromRequest<S> for JsonStream<T>
+where
+    T: DeserializeOwned + 'static,
+    S: 'static,
+{
+    type Config = JsonConfig<S>;
+    type Result = FutureResult<Self, Error>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
+        let mut stream = JsonStream::new(req).limit(cfg.limit);
+        match stream.err.take() {
+            Some(err) => future::err((*cfg.ehandler)(err, req.clone())),
+            None => future::ok(stream),
+        }
+    }
+}
+
+/// Newline delimited json responder
+///
+/// Every item of the stream is serialized to a single line of
+/// `application/x-ndjson` response, response body is streamed.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// # extern crate futures;
+/// #[macro_use] extern crate serde_derive;
+/// use actix_web::{App, Error, HttpRequest, NdJson};
+/// use futures::{stream, Stream};
+///
+/// #[derive(Serialize)]
+/// struct Event {
+///     id: u32,
+/// }
+///
+/// fn index(_req: HttpRequest) -> NdJson<Box<Stream<Item = Event, Error = Error>>> {
+///     NdJson(Box::new(stream::iter_ok::<_, Error>(
+///         (0..10).map(|id| Event { id }),
+///     )))
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource("/events", |r| r.f(index));
+/// }
+/// ```
+pub struct NdJson<S>(pub S);
+
+impl<S, T, E> Responder for NdJson<S>
+where
+    S: Stream<Item = T, Error = E> + 'static,
+    T: Serialize + 'static,
+    E: Into<Error> + 'static,
+{
+    type Item = HttpResponse;
+    type Error = Error;
+
+    fn respond_to(self, req: HttpRequest) -> Result<HttpResponse, Error> {
+        let body = self.0.map_err(|e| -> Error { e.into() }).and_then(
+            |item| -> Result<Bytes, Error> {
+                let mut line = serde_json::to_vec(&item)?;
+                line.push(b'\n');
+                Ok(Bytes::from(line))
+            },
+        );
+
+        Ok(req.build_response(StatusCode::OK)
+            .content_type("application/x-ndjson")
+            .streaming(body))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use body::Body;
     use bytes::Bytes;
     use futures::Async;
     use http::header;
@@ -408,6 +618,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_json_stream() {
+        let req = HttpRequest::default();
+        let mut stream = JsonStream::<MyObject>::new(&req);
+        assert_eq!(stream.poll().err().unwrap(), JsonPayloadError::ContentType);
+
+        let mut req = HttpRequest::default();
+        req.headers_mut().insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/x-ndjson"),
+        );
+        req.payload_mut().unread_data(Bytes::from_static(
+            b"{\"name\": \"test1\"}\n\r\n{\"name\": \"test2\"}",
+        ));
+        let mut stream = JsonStream::<MyObject>::new(&req);
+        assert_eq!(
+            stream.poll().ok().unwrap(),
+            Async::Ready(Some(MyObject {
+                name: "test1".to_owned()
+            }))
+        );
+        assert_eq!(
+            stream.poll().ok().unwrap(),
+            Async::Ready(Some(MyObject {
+                name: "test2".to_owned()
+            }))
+        );
+        assert_eq!(stream.poll().ok().unwrap(), Async::Ready(None));
+
+        let mut req = HttpRequest::default();
+        req.headers_mut().insert(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("application/x-ndjson"),
+        );
+        req.payload_mut()
+            .unread_data(Bytes::from_static(b"{\"name\": \"test\"}\n"));
+        let mut stream = JsonStream::<MyObject>::new(&req).limit(10);
+        assert_eq!(stream.poll().err().unwrap(), JsonPayloadError::Overflow);
+    }
+
+    #[test]
+    fn test_ndjson() {
+        use futures::stream;
+
+        let items = vec![
+            MyObject {
+                name: "test1".to_owned(),
+            },
+            MyObject {
+                name: "test2".to_owned(),
+            },
+        ];
+        let mut resp = NdJson(stream::iter_ok::<_, Error>(items))
+            .respond_to(HttpRequest::default())
+            .unwrap();
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/x-ndjson"
+        );
+        match resp.replace_body(Body::Empty) {
+            Body::Streaming(s) => assert_eq!(
+                s.concat2().wait().unwrap(),
+                Bytes::from_static(b"{\"name\":\"test1\"}\n{\"name\":\"test2\"}\n")
+            ),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn test_with_json() {
         let mut cfg = ExtractorConfig::<_, Json<MyObject>>::default();
@@ -103,6 +103,7 @@ extern crate serde;
 #[cfg(feature = "brotli")]
 extern crate brotli2;
 extern crate encoding;
+extern crate erased_serde;
 extern crate flate2;
 extern crate h2 as http2;
 extern crate num_cpus;
@@ -141,12 +142,14 @@ mod httprequest;
 mod httpresponse;
 mod info;
 mod json;
+mod negotiate;
 mod param;
 mod payload;
 mod pipeline;
 mod resource;
 mod route;
 mod router;
+mod validate;
 mod with;
 
 pub mod client;
@@ -154,6 +157,7 @@ pub mod error;
 pub mod fs;
 pub mod middleware;
 pub mod multipart;
+pub mod openapi;
 pub mod pred;
 pub mod server;
 pub mod test;
@@ -162,12 +166,15 @@ pub use application::App;
 pub use body::{Binary, Body};
 pub use context::HttpContext;
 pub use error::{Error, ResponseError, Result};
-pub use extractor::{Form, Path, Query};
+pub use extractor::{Form, Header, MultipartForm, Path, Query};
 pub use handler::{AsyncResponder, Either, FromRequest, FutureResponse, Responder, State};
 pub use httpmessage::HttpMessage;
 pub use httprequest::HttpRequest;
 pub use httpresponse::HttpResponse;
-pub use json::Json;
+pub use json::{Json, JsonStream, NdJson};
+pub use negotiate::Negotiated;
+pub use param::PathTail;
+pub use validate::{Validate, Validated};
 
 #[doc(hidden)]
 pub mod httpcodes;
@@ -199,16 +206,20 @@ pub mod dev {
 
     pub use body::BodyStream;
     pub use context::Drain;
-    pub use extractor::{FormConfig, PayloadConfig};
+    pub use extractor::{FormConfig, HeaderConfig, MultipartFormConfig, PathConfig,
+                        PayloadConfig, QueryConfig};
     pub use handler::{Handler, Reply};
     pub use httpmessage::{MessageBody, UrlEncoded};
     pub use httpresponse::HttpResponseBuilder;
     pub use info::ConnectionInfo;
     pub use json::{JsonBody, JsonConfig};
+    pub use negotiate::ResponseFormat;
     pub use param::{FromParam, Params};
     pub use resource::ResourceHandler;
     pub use route::Route;
-    pub use router::{Resource, ResourceType, Router};
+    pub use router::{ConflictPolicy, Resource, ResourceType, RouteConflict, RouteInfo,
+                     Router};
+    pub use validate::ValidatedFromRequest;
 }
 
 pub mod http {
@@ -223,7 +234,7 @@ pub mod http {
     pub use cookie::{Cookie, CookieBuilder};
     pub use http_range::HttpRange;
 
-    pub use helpers::NormalizePath;
+    pub use helpers::{NormalizePath, PathPolicy, TrailingSlash};
 
     pub mod header {
         pub use header::*;
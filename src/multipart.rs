@@ -1,8 +1,11 @@
 //! Multipart requests support
 use std::cell::RefCell;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
 use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use std::{cmp, fmt};
+use std::{cmp, fmt, io};
 
 use bytes::Bytes;
 use futures::task::{current as current_task, Task};
@@ -11,6 +14,7 @@ use http::HttpTryFrom;
 use http::header::{self, HeaderMap, HeaderName, HeaderValue};
 use httparse;
 use mime;
+use rand::{self, Rng};
 
 use error::{MultipartError, ParseError, PayloadError};
 use payload::PayloadHelper;
@@ -408,6 +412,71 @@ where
     pub fn content_type(&self) -> &mime::Mime {
         &self.ct
     }
+
+    /// Returns `name` parameter of field's `Content-Disposition` header
+    pub fn field_name(&self) -> Option<String> {
+        disposition_param(&self.headers, "name")
+    }
+
+    /// Returns `filename` parameter of field's `Content-Disposition` header
+    pub fn file_name(&self) -> Option<String> {
+        disposition_param(&self.headers, "filename")
+    }
+}
+
+/// Find parameter of `Content-Disposition` header, quoted values are unescaped.
+fn disposition_param(headers: &HeaderMap, param: &str) -> Option<String> {
+    let value = match headers.get(header::CONTENT_DISPOSITION) {
+        Some(value) => match value.to_str() {
+            Ok(value) => value,
+            Err(_) => return None,
+        },
+        None => return None,
+    };
+
+    // skip disposition type
+    let mut rest = match value.find(';') {
+        Some(pos) => &value[pos + 1..],
+        None => return None,
+    };
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        rest = rest[eq + 1..].trim_left();
+
+        let (val, tail) = if rest.starts_with('"') {
+            let mut val = String::new();
+            let mut end = None;
+            let mut chars = rest[1..].char_indices();
+            while let Some((idx, ch)) = chars.next() {
+                match ch {
+                    '\\' => if let Some((_, ch)) = chars.next() {
+                        val.push(ch)
+                    },
+                    '"' => {
+                        end = Some(idx + 2);
+                        break;
+                    }
+                    _ => val.push(ch),
+                }
+            }
+            match end {
+                Some(end) => (val, &rest[end..]),
+                None => return None,
+            }
+        } else {
+            let end = rest.find(';').unwrap_or_else(|| rest.len());
+            (rest[..end].trim().to_owned(), &rest[end..])
+        };
+
+        if key.to_lowercase() == param {
+            return Some(val);
+        }
+        rest = match tail.find(';') {
+            Some(pos) => &tail[pos + 1..],
+            None => return None,
+        };
+    }
+    None
 }
 
 impl<S> Stream for Field<S>
@@ -658,6 +727,177 @@ impl Drop for Safety {
     }
 }
 
+/// Multipart form file, see `MultipartForm` extractor.
+///
+/// Small files are kept in memory, larger files are spooled to a temporary
+/// file, which is removed on drop unless it gets persisted.
+#[derive(Debug)]
+pub struct MultipartFile {
+    field_name: String,
+    file_name: Option<String>,
+    content_type: mime::Mime,
+    size: usize,
+    data: FileData,
+}
+
+#[derive(Debug)]
+enum FileData {
+    Memory(Bytes),
+    Spooled(TempFile),
+}
+
+impl MultipartFile {
+    pub(crate) fn memory(
+        field_name: String, file_name: Option<String>, content_type: mime::Mime,
+        data: Bytes,
+    ) -> MultipartFile {
+        MultipartFile {
+            field_name,
+            file_name,
+            content_type,
+            size: data.len(),
+            data: FileData::Memory(data),
+        }
+    }
+
+    pub(crate) fn spooled(
+        field_name: String, file_name: Option<String>, content_type: mime::Mime,
+        size: usize, file: TempFile,
+    ) -> MultipartFile {
+        MultipartFile {
+            field_name,
+            file_name,
+            content_type,
+            size,
+            data: FileData::Spooled(file),
+        }
+    }
+
+    /// Form field name
+    pub fn field_name(&self) -> &str {
+        &self.field_name
+    }
+
+    /// File name provided by the client
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_ref().map(|s| s.as_str())
+    }
+
+    /// File content type
+    pub fn content_type(&self) -> &mime::Mime {
+        &self.content_type
+    }
+
+    /// File size in bytes
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// File content, if file is kept in memory
+    pub fn bytes(&self) -> Option<&Bytes> {
+        match self.data {
+            FileData::Memory(ref bytes) => Some(bytes),
+            FileData::Spooled(_) => None,
+        }
+    }
+
+    /// Path of the temporary file, if file is spooled to disk
+    pub fn path(&self) -> Option<&Path> {
+        match self.data {
+            FileData::Memory(_) => None,
+            FileData::Spooled(ref file) => Some(&file.path),
+        }
+    }
+
+    /// Read file content
+    pub fn read(&self) -> io::Result<Bytes> {
+        match self.data {
+            FileData::Memory(ref bytes) => Ok(bytes.clone()),
+            FileData::Spooled(ref file) => {
+                let mut buf = Vec::with_capacity(self.size);
+                File::open(&file.path)?.read_to_end(&mut buf)?;
+                Ok(Bytes::from(buf))
+            }
+        }
+    }
+
+    /// Store file at `path`
+    ///
+    /// On unix spooled file is moved with owner only permissions,
+    /// change them if file has to be readable by other users.
+    pub fn persist<P: AsRef<Path>>(self, path: P) -> io::Result<()> {
+        match self.data {
+            FileData::Memory(ref bytes) => File::create(path)?.write_all(bytes),
+            FileData::Spooled(mut file) => {
+                if fs::rename(&file.path, path.as_ref()).is_err() {
+                    // different file systems
+                    fs::copy(&file.path, path.as_ref())?;
+                } else {
+                    file.persisted = true;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Temporary file, removed on drop
+#[derive(Debug)]
+pub(crate) struct TempFile {
+    path: PathBuf,
+    file: File,
+    persisted: bool,
+}
+
+impl TempFile {
+    /// Create new temporary file in `dir`, on unix file is readable
+    /// and writable only by the owner
+    pub fn new(dir: &Path) -> io::Result<TempFile> {
+        loop {
+            let name: String = rand::thread_rng()
+                .gen_ascii_chars()
+                .take(16)
+                .collect();
+            let path = dir.join(format!("actix-web-{}", name));
+            let mut options = OpenOptions::new();
+            options.write(true).create_new(true);
+            owner_only(&mut options);
+            match options.open(&path) {
+                Ok(file) => {
+                    return Ok(TempFile {
+                        path,
+                        file,
+                        persisted: false,
+                    })
+                }
+                Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    pub fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        self.file.write_all(data)
+    }
+}
+
+#[cfg(unix)]
+fn owner_only(options: &mut OpenOptions) {
+    use std::os::unix::fs::OpenOptionsExt;
+    options.mode(0o600);
+}
+
+#[cfg(not(unix))]
+fn owner_only(_: &mut OpenOptions) {}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if !self.persisted {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -785,4 +1025,15 @@ mod tests {
             }))
             .unwrap();
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_temp_file_permissions() {
+        use std::env;
+        use std::os::unix::fs::PermissionsExt;
+
+        let file = TempFile::new(&env::temp_dir()).unwrap();
+        let mode = fs::metadata(&file.path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
 }
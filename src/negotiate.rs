@@ -0,0 +1,269 @@
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+use erased_serde;
+use http::header::{self, HeaderValue};
+use http::StatusCode;
+use mime::{self, Mime};
+use serde::Serialize;
+use serde_json::{self, Value};
+use serde_urlencoded;
+
+use body::Binary;
+use error::{Error, NegotiationError};
+use handler::Responder;
+use header::{Accept, Header};
+use httprequest::HttpRequest;
+use httpresponse::HttpResponse;
+
+/// Response body serializer, serializes value to a specific format
+pub type ResponseFormat = Rc<Fn(&erased_serde::Serialize) -> Result<Binary, Error>>;
+
+/// Content negotiation responder
+///
+/// Value is serialized to the format that is preferred by `Accept`
+/// request header. Available formats are `application/json`,
+/// `application/x-www-form-urlencoded`, `text/plain` and formats that
+/// are registered with `App::response_format()`. If client does not
+/// accept any of them, `406 Not Acceptable` response is returned.
+///
+/// Requests without `Accept` header get `application/json` response.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// #[macro_use] extern crate serde_derive;
+/// use actix_web::{App, HttpRequest, Negotiated, Result};
+///
+/// #[derive(Serialize)]
+/// struct MyObj {
+///     name: String,
+/// }
+///
+/// fn index(req: HttpRequest) -> Result<Negotiated<MyObj>> {
+///     Ok(Negotiated(MyObj {
+///         name: req.match_info().query("name")?,
+///     }))
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource(r"/a/{name}", |r| r.f(index));
+/// }
+/// ```
+pub struct Negotiated<T>(pub T);
+
+impl<T> Negotiated<T> {
+    /// Deconstruct to an inner value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Negotiated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Negotiated<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Serialize> Responder for Negotiated<T> {
+    type Item = HttpResponse;
+    type Error = Error;
+
+    fn respond_to(self, req: HttpRequest) -> Result<HttpResponse, Error> {
+        // application formats are prepared when router is built
+        let (ct, format) = match req.router() {
+            Some(router) => select(&req, router.response_formats())?,
+            None => select(&req, &default_formats(&[]))?,
+        };
+        let body = (*format)(&self.0)?;
+
+        let mut resp = req.build_response(StatusCode::OK)
+            .content_type(ct.as_ref())
+            .body(body);
+        resp.headers_mut()
+            .insert(header::VARY, HeaderValue::from_static("accept"));
+        Ok(resp)
+    }
+}
+
+/// Format that is preferred by the request
+fn select<S>(
+    req: &HttpRequest<S>, formats: &[(Mime, ResponseFormat)]
+) -> Result<(Mime, ResponseFormat), Error> {
+    match negotiate(req, formats) {
+        Some(idx) => Ok(formats[idx].clone()),
+        None => Err(NegotiationError::NotAcceptable.into()),
+    }
+}
+
+/// Built-in formats, followed by application formats.
+/// Application format replaces built-in format with the same mime type.
+pub(crate) fn default_formats(
+    registered: &[(Mime, ResponseFormat)]
+) -> Vec<(Mime, ResponseFormat)> {
+    let builtin = vec![
+        (mime::APPLICATION_JSON, Rc::new(json) as ResponseFormat),
+        (
+            mime::APPLICATION_WWW_FORM_URLENCODED,
+            Rc::new(urlencoded) as ResponseFormat,
+        ),
+        (mime::TEXT_PLAIN_UTF_8, Rc::new(plain_text) as ResponseFormat),
+    ];
+
+    let mut formats: Vec<(Mime, ResponseFormat)> = builtin
+        .into_iter()
+        .map(|(ct, format)| {
+            match registered.iter().find(|&&(ref m, _)| same_type(m, &ct)) {
+                Some(&(ref m, ref f)) => (m.clone(), Rc::clone(f)),
+                None => (ct, format),
+            }
+        })
+        .collect();
+    for &(ref ct, ref format) in registered {
+        if !formats.iter().any(|&(ref m, _)| same_type(m, ct)) {
+            formats.push((ct.clone(), Rc::clone(format)));
+        }
+    }
+    formats
+}
+
+fn json(value: &erased_serde::Serialize) -> Result<Binary, Error> {
+    Ok(serde_json::to_string(&value)?.into())
+}
+
+fn urlencoded(value: &erased_serde::Serialize) -> Result<Binary, Error> {
+    Ok(serde_urlencoded::to_string(&value)?.into())
+}
+
+/// Strings are sent as is, other values are sent as pretty printed json
+fn plain_text(value: &erased_serde::Serialize) -> Result<Binary, Error> {
+    match serde_json::to_value(&value)? {
+        Value::String(s) => Ok(s.into()),
+        value @ Value::Array(_) | value @ Value::Object(_) => {
+            Ok(serde_json::to_string_pretty(&value)?.into())
+        }
+        value => Ok(value.to_string().into()),
+    }
+}
+
+fn same_type(a: &Mime, b: &Mime) -> bool {
+    a.type_() == b.type_() && a.subtype() == b.subtype()
+}
+
+/// Index of the best format for the request, see `Accept::preferred()`.
+/// The first format is used if `Accept` header is missing or malformed.
+fn negotiate<S>(
+    req: &HttpRequest<S>, formats: &[(Mime, ResponseFormat)]
+) -> Option<usize> {
+    if formats.is_empty() {
+        return None;
+    }
+    let accept = match Accept::parse(req) {
+        Ok(ref accept) if accept.is_empty() => return Some(0),
+        Ok(accept) => accept,
+        Err(_) => return Some(0),
+    };
+    let available: Vec<Mime> = formats.iter().map(|&(ref ct, _)| ct.clone()).collect();
+    accept.preferred(&available)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test::TestRequest;
+
+    #[derive(Serialize)]
+    struct MyObject {
+        name: &'static str,
+    }
+
+    fn respond(accept: Option<&str>) -> Result<HttpResponse, Error> {
+        let req = match accept {
+            Some(accept) => TestRequest::with_header("accept", accept).finish(),
+            None => TestRequest::default().finish(),
+        };
+        Negotiated(MyObject { name: "test" }).respond_to(req)
+    }
+
+    fn content_type(resp: &HttpResponse) -> &str {
+        resp.headers()
+            .get(header::CONTENT_TYPE)
+            .unwrap()
+            .to_str()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let resp = respond(None).unwrap();
+        assert_eq!(content_type(&resp), "application/json");
+        assert_eq!(resp.headers().get(header::VARY).unwrap(), "accept");
+        assert_eq!(
+            resp.body().binary().unwrap(),
+            &Binary::from(r#"{"name":"test"}"#)
+        );
+
+        let resp = respond(Some("text/html, */*;q=0.1")).unwrap();
+        assert_eq!(content_type(&resp), "application/json");
+
+        let resp = respond(Some("text/*")).unwrap();
+        assert_eq!(content_type(&resp), "text/plain; charset=utf-8");
+
+        let resp = respond(Some(
+            "application/json;q=0.5, application/x-www-form-urlencoded",
+        )).unwrap();
+        assert_eq!(content_type(&resp), "application/x-www-form-urlencoded");
+        assert_eq!(resp.body().binary().unwrap(), &Binary::from("name=test"));
+
+        let resp = respond(Some("*/*, application/json;q=0")).unwrap();
+        assert_eq!(content_type(&resp), "application/x-www-form-urlencoded");
+
+        let err = respond(Some("text/html, image/*")).err().unwrap();
+        assert_eq!(
+            err.cause().error_response().status(),
+            StatusCode::NOT_ACCEPTABLE
+        );
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(plain_text(&"text").unwrap(), Binary::from("text"));
+        assert_eq!(plain_text(&10).unwrap(), Binary::from("10"));
+    }
+
+    #[test]
+    fn test_default_formats() {
+        let registered = vec![
+            (
+                "application/json; charset=utf-8".parse().unwrap(),
+                Rc::new(|_: &erased_serde::Serialize| Ok("json".into()))
+                    as ResponseFormat,
+            ),
+            (
+                "application/cbor".parse().unwrap(),
+                Rc::new(|_: &erased_serde::Serialize| Ok("cbor".into()))
+                    as ResponseFormat,
+            ),
+        ];
+        let formats = default_formats(&registered);
+        assert_eq!(
+            formats
+                .iter()
+                .map(|&(ref mime, _)| mime.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "application/json; charset=utf-8",
+                "application/x-www-form-urlencoded",
+                "text/plain; charset=utf-8",
+                "application/cbor",
+            ]
+        );
+    }
+}
@@ -0,0 +1,791 @@
+//! OpenAPI document generation
+//!
+//! Routes are described with `Route::doc()`, request part of operation
+//! is built from extractor types, the same types that are used with
+//! `Route::with()`. Only documented routes with explicit methods are
+//! included into document.
+//!
+//! ```rust
+//! # extern crate actix_web;
+//! # extern crate serde_json;
+//! #[macro_use] extern crate serde_derive;
+//! use actix_web::openapi::{ObjectSchema, OpenApi, Operation, Schema};
+//! use actix_web::{http, App, Json, Path};
+//! use serde_json::Value;
+//!
+//! #[derive(Deserialize, Serialize)]
+//! struct User {
+//!     name: String,
+//!     age: Option<u32>,
+//! }
+//!
+//! impl Schema for User {
+//!     fn schema() -> Value {
+//!         ObjectSchema::new()
+//!             .required("name", String::schema())
+//!             .optional("age", u32::schema())
+//!             .into_value()
+//!     }
+//! }
+//!
+//! fn update(info: (Path<u64>, Json<User>)) -> Json<User> {
+//!     info.1
+//! }
+//!
+//! fn main() {
+//!     let app = App::new()
+//!         .resource("/user/{id}", |r| {
+//!             r.method(http::Method::PUT)
+//!                 .doc(
+//!                     Operation::new()
+//!                         .summary("Update user")
+//!                         .request::<(Path<u64>, Json<User>)>()
+//!                         .response::<User>(http::StatusCode::OK, "Updated user"),
+//!                 )
+//!                 .with(update);
+//!         })
+//!         .openapi("/openapi.json", OpenApi::new("Users", "1.0"));
+//! }
+//! ```
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use http::StatusCode;
+use serde_json::{Map, Value};
+
+use error::Error;
+use extractor::{Form, Header, MultipartForm, Path, Query};
+use handler::State;
+use header;
+use httprequest::HttpRequest;
+use json::{Json, JsonStream};
+use router::RouteInfo;
+use validate::Validated;
+
+/// Type that could be described with JSON schema
+pub trait Schema {
+    /// JSON schema of the type
+    fn schema() -> Value;
+}
+
+/// Extractor that could describe request part of an operation,
+/// i.e. parameters and request body
+pub trait ApiExtractor {
+    /// Add extractor's parameters or request body to operation
+    fn operation(op: Operation) -> Operation;
+}
+
+/// Builder for object schemas
+#[derive(Clone, Debug, Default)]
+pub struct ObjectSchema {
+    properties: Map<String, Value>,
+    required: Vec<Value>,
+}
+
+impl ObjectSchema {
+    /// Create object schema without properties
+    pub fn new() -> ObjectSchema {
+        ObjectSchema::default()
+    }
+
+    /// Add required property
+    pub fn required(mut self, name: &str, schema: Value) -> Self {
+        self.required.push(Value::String(name.to_owned()));
+        self.properties.insert(name.to_owned(), schema);
+        self
+    }
+
+    /// Add optional property
+    pub fn optional(mut self, name: &str, schema: Value) -> Self {
+        self.properties.insert(name.to_owned(), schema);
+        self
+    }
+
+    /// Build schema
+    pub fn into_value(self) -> Value {
+        let mut schema = Map::new();
+        schema.insert("type".to_owned(), "object".into());
+        schema.insert("properties".to_owned(), Value::Object(self.properties));
+        if !self.required.is_empty() {
+            schema.insert("required".to_owned(), Value::Array(self.required));
+        }
+        Value::Object(schema)
+    }
+}
+
+/// Description of a route, OpenAPI operation object
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Operation {
+    summary: Option<String>,
+    description: Option<String>,
+    operation_id: Option<String>,
+    tags: Vec<String>,
+    deprecated: bool,
+    path: Option<Value>,
+    parameters: Vec<Value>,
+    body: Option<(Map<String, Value>, bool)>,
+    responses: Map<String, Value>,
+}
+
+impl Operation {
+    /// Create empty operation
+    pub fn new() -> Operation {
+        Operation::default()
+    }
+
+    /// Set short summary
+    pub fn summary<T: Into<String>>(mut self, summary: T) -> Self {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// Set verbose description
+    pub fn description<T: Into<String>>(mut self, description: T) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set unique operation id
+    pub fn operation_id<T: Into<String>>(mut self, id: T) -> Self {
+        self.operation_id = Some(id.into());
+        self
+    }
+
+    /// Add tag
+    pub fn tag<T: Into<String>>(mut self, tag: T) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Mark operation as deprecated
+    pub fn deprecated(mut self) -> Self {
+        self.deprecated = true;
+        self
+    }
+
+    /// Describe request with extractor type, i.e.
+    /// `request::<(Path<u64>, Json<User>)>()`
+    pub fn request<T: ApiExtractor>(self) -> Self {
+        T::operation(self)
+    }
+
+    /// Set schema of path parameters.
+    ///
+    /// Properties of object schema are matched with pattern's parameters
+    /// by name, any other schema is used for all parameters.
+    /// Parameters without schema are described as strings.
+    pub fn path_schema(mut self, schema: Value) -> Self {
+        self.path = Some(schema);
+        self
+    }
+
+    /// Add parameter, location is one of `query`, `header` or `cookie`
+    pub fn parameter(
+        mut self, name: &str, location: &str, schema: Value, required: bool
+    ) -> Self {
+        self.parameters
+            .push(parameter(name, location, schema, required));
+        self
+    }
+
+    /// Add request body content type
+    pub fn request_body(mut self, content_type: &str, schema: Value) -> Self {
+        let mut content = Map::new();
+        content.insert("schema".to_owned(), schema);
+        if self.body.is_none() {
+            self.body = Some((Map::new(), true));
+        }
+        if let Some((ref mut body, _)) = self.body {
+            body.insert(content_type.to_owned(), Value::Object(content));
+        }
+        self
+    }
+
+    /// Add `application/json` response
+    pub fn response<T: Schema>(self, status: StatusCode, description: &str) -> Self {
+        self.response_content(status, description, "application/json", T::schema())
+    }
+
+    /// Add response with specific content type
+    pub fn response_content(
+        mut self, status: StatusCode, description: &str, content_type: &str,
+        schema: Value,
+    ) -> Self {
+        let mut content = Map::new();
+        content.insert("schema".to_owned(), schema);
+
+        let mut response = match self.responses.remove(status.as_str()) {
+            Some(Value::Object(response)) => response,
+            _ => Map::new(),
+        };
+        response.insert("description".to_owned(), description.into());
+        let mut contents = match response.remove("content") {
+            Some(Value::Object(contents)) => contents,
+            _ => Map::new(),
+        };
+        contents.insert(content_type.to_owned(), Value::Object(content));
+        response.insert("content".to_owned(), Value::Object(contents));
+        self.responses
+            .insert(status.as_str().to_owned(), Value::Object(response));
+        self
+    }
+
+    /// Add response without body
+    pub fn status(mut self, status: StatusCode, description: &str) -> Self {
+        let mut response = Map::new();
+        response.insert("description".to_owned(), description.into());
+        self.responses
+            .insert(status.as_str().to_owned(), Value::Object(response));
+        self
+    }
+
+    /// Operation object for a route, `names` are pattern's parameters
+    fn to_value(&self, names: &[String]) -> Value {
+        let mut op = Map::new();
+        if let Some(ref summary) = self.summary {
+            op.insert("summary".to_owned(), summary.as_str().into());
+        }
+        if let Some(ref description) = self.description {
+            op.insert("description".to_owned(), description.as_str().into());
+        }
+        if let Some(ref id) = self.operation_id {
+            op.insert("operationId".to_owned(), id.as_str().into());
+        }
+        if !self.tags.is_empty() {
+            let tags = self.tags.iter().map(|t| t.as_str().into()).collect();
+            op.insert("tags".to_owned(), Value::Array(tags));
+        }
+        if self.deprecated {
+            op.insert("deprecated".to_owned(), true.into());
+        }
+
+        let mut params: Vec<_> = names
+            .iter()
+            .map(|name| {
+                let schema = match self.path {
+                    Some(Value::Object(ref schema))
+                        if schema.get("type") == Some(&"object".into()) =>
+                    {
+                        schema
+                            .get("properties")
+                            .and_then(|props| props.get(name.as_str()))
+                            .cloned()
+                    }
+                    Some(ref schema) => Some(schema.clone()),
+                    None => None,
+                };
+                let schema = schema.unwrap_or_else(String::schema);
+                parameter(name, "path", schema, true)
+            })
+            .collect();
+        params.extend(self.parameters.iter().cloned());
+        if !params.is_empty() {
+            op.insert("parameters".to_owned(), Value::Array(params));
+        }
+
+        if let Some((ref content, required)) = self.body {
+            let mut body = Map::new();
+            body.insert("content".to_owned(), Value::Object(content.clone()));
+            body.insert("required".to_owned(), required.into());
+            op.insert("requestBody".to_owned(), Value::Object(body));
+        }
+
+        let responses = if self.responses.is_empty() {
+            let mut response = Map::new();
+            response.insert("description".to_owned(), "Default response".into());
+            let mut responses = Map::new();
+            responses.insert("default".to_owned(), Value::Object(response));
+            responses
+        } else {
+            self.responses.clone()
+        };
+        op.insert("responses".to_owned(), Value::Object(responses));
+        Value::Object(op)
+    }
+}
+
+fn parameter(name: &str, location: &str, schema: Value, required: bool) -> Value {
+    let mut param = Map::new();
+    param.insert("name".to_owned(), name.into());
+    param.insert("in".to_owned(), location.into());
+    param.insert("required".to_owned(), required.into());
+    param.insert("schema".to_owned(), schema);
+    Value::Object(param)
+}
+
+/// Parameters for object schema properties
+fn parameters(op: Operation, location: &str, schema: &Value) -> Operation {
+    let required = schema
+        .get("required")
+        .and_then(|required| required.as_array())
+        .cloned()
+        .unwrap_or_else(Vec::new);
+    let props = match schema.get("properties").and_then(|p| p.as_object()) {
+        Some(props) => props,
+        None => return op,
+    };
+    props.iter().fold(op, |op, (name, schema)| {
+        let is_required = required.contains(&Value::String(name.clone()));
+        op.parameter(name, location, schema.clone(), is_required)
+    })
+}
+
+/// OpenAPI path template and names of pattern's parameters,
+/// custom regexes and `*`, `?` modifiers are removed, so pattern
+/// with optional parameters is documented with all parameters present
+fn path_template(path: &str) -> (String, Vec<String>) {
+    let mut template = String::new();
+    let mut names = Vec::new();
+    let mut name = String::new();
+    let mut in_param = false;
+    let mut in_pattern = false;
+    let mut after_param = false;
+
+    for ch in path.chars() {
+        if in_param {
+            if ch == '}' {
+                template.push('{');
+                template.push_str(&name);
+                template.push('}');
+                names.push(name.clone());
+                name.clear();
+                in_param = false;
+                in_pattern = false;
+                after_param = true;
+                continue;
+            } else if ch == ':' {
+                in_pattern = true;
+            } else if !in_pattern {
+                name.push(ch);
+            }
+        } else if ch == '{' {
+            in_param = true;
+        } else if !(after_param && (ch == '*' || ch == '?')) {
+            template.push(ch);
+        }
+        after_param = false;
+    }
+    (template, names)
+}
+
+/// OpenAPI document description
+///
+/// Document could be served with `App::openapi()` or generated
+/// from application's route table with `OpenApi::document()`.
+#[derive(Clone, Debug)]
+pub struct OpenApi {
+    title: String,
+    version: String,
+    description: Option<String>,
+    servers: Vec<String>,
+}
+
+impl OpenApi {
+    /// Create document description with api title and version
+    pub fn new<T: Into<String>, V: Into<String>>(title: T, version: V) -> OpenApi {
+        OpenApi {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            servers: Vec::new(),
+        }
+    }
+
+    /// Set api description
+    pub fn description<T: Into<String>>(mut self, description: T) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add server url
+    pub fn server<T: Into<String>>(mut self, url: T) -> Self {
+        self.servers.push(url.into());
+        self
+    }
+
+    /// Generate OpenAPI 3 document for the route table
+    pub fn document(&self, routes: &[RouteInfo]) -> Value {
+        let mut paths = Map::new();
+        for route in routes {
+            let (op, methods) = match (route.operation(), route.methods()) {
+                (Some(op), Some(methods)) => (op, methods),
+                _ => continue,
+            };
+            let (template, names) = path_template(&route.path());
+            let mut item = match paths.remove(&template) {
+                Some(Value::Object(item)) => item,
+                _ => Map::new(),
+            };
+            for method in methods {
+                item.insert(method.as_str().to_lowercase(), op.to_value(&names));
+            }
+            paths.insert(template, Value::Object(item));
+        }
+
+        let mut info = Map::new();
+        info.insert("title".to_owned(), self.title.as_str().into());
+        info.insert("version".to_owned(), self.version.as_str().into());
+        if let Some(ref description) = self.description {
+            info.insert("description".to_owned(), description.as_str().into());
+        }
+
+        let mut doc = Map::new();
+        doc.insert("openapi".to_owned(), "3.0.0".into());
+        doc.insert("info".to_owned(), Value::Object(info));
+        if !self.servers.is_empty() {
+            let servers = self.servers
+                .iter()
+                .map(|url| {
+                    let mut server = Map::new();
+                    server.insert("url".to_owned(), url.as_str().into());
+                    Value::Object(server)
+                })
+                .collect();
+            doc.insert("servers".to_owned(), Value::Array(servers));
+        }
+        doc.insert("paths".to_owned(), Value::Object(paths));
+        Value::Object(doc)
+    }
+}
+
+fn primitive(tp: &str, format: Option<&str>) -> Value {
+    let mut schema = Map::new();
+    schema.insert("type".to_owned(), tp.into());
+    if let Some(format) = format {
+        schema.insert("format".to_owned(), format.into());
+    }
+    Value::Object(schema)
+}
+
+macro_rules! primitive_schema {
+    ($($t:ty => $tp:expr, $format:expr;)+) => {
+        $(
+            impl Schema for $t {
+                fn schema() -> Value {
+                    primitive($tp, $format)
+                }
+            }
+        )+
+    };
+}
+
+primitive_schema! {
+    bool => "boolean", None;
+    i8 => "integer", Some("int32");
+    i16 => "integer", Some("int32");
+    i32 => "integer", Some("int32");
+    i64 => "integer", Some("int64");
+    isize => "integer", Some("int64");
+    u8 => "integer", Some("int32");
+    u16 => "integer", Some("int32");
+    u32 => "integer", Some("int64");
+    u64 => "integer", Some("int64");
+    usize => "integer", Some("int64");
+    f32 => "number", Some("float");
+    f64 => "number", Some("double");
+    char => "string", None;
+    String => "string", None;
+}
+
+impl<T: Schema> Schema for Option<T> {
+    fn schema() -> Value {
+        T::schema()
+    }
+}
+
+impl<T: Schema> Schema for Vec<T> {
+    fn schema() -> Value {
+        let mut schema = Map::new();
+        schema.insert("type".to_owned(), "array".into());
+        schema.insert("items".to_owned(), T::schema());
+        Value::Object(schema)
+    }
+}
+
+impl<T: Schema> Schema for HashMap<String, T> {
+    fn schema() -> Value {
+        let mut schema = Map::new();
+        schema.insert("type".to_owned(), "object".into());
+        schema.insert("additionalProperties".to_owned(), T::schema());
+        Value::Object(schema)
+    }
+}
+
+/// Any value
+impl Schema for Value {
+    fn schema() -> Value {
+        Value::Object(Map::new())
+    }
+}
+
+impl<T: Schema> ApiExtractor for Path<T> {
+    fn operation(op: Operation) -> Operation {
+        op.path_schema(T::schema())
+    }
+}
+
+impl<T: Schema> ApiExtractor for Query<T> {
+    fn operation(op: Operation) -> Operation {
+        parameters(op, "query", &T::schema())
+    }
+}
+
+impl<T: Schema> ApiExtractor for Json<T> {
+    fn operation(op: Operation) -> Operation {
+        op.request_body("application/json", T::schema())
+    }
+}
+
+impl<T: Schema> ApiExtractor for JsonStream<T> {
+    fn operation(op: Operation) -> Operation {
+        op.request_body("application/x-ndjson", T::schema())
+    }
+}
+
+impl<T: Schema> ApiExtractor for Form<T> {
+    fn operation(op: Operation) -> Operation {
+        op.request_body("application/x-www-form-urlencoded", T::schema())
+    }
+}
+
+impl<T: Schema> ApiExtractor for MultipartForm<T> {
+    fn operation(op: Operation) -> Operation {
+        op.request_body("multipart/form-data", T::schema())
+    }
+}
+
+impl<H: header::Header> ApiExtractor for Header<H> {
+    fn operation(op: Operation) -> Operation {
+        op.parameter(H::name().as_str(), "header", String::schema(), true)
+    }
+}
+
+impl<T: ApiExtractor> ApiExtractor for Validated<T> {
+    fn operation(op: Operation) -> Operation {
+        T::operation(op)
+    }
+}
+
+impl<T: ApiExtractor> ApiExtractor for Result<T, Error> {
+    fn operation(op: Operation) -> Operation {
+        T::operation(op)
+    }
+}
+
+/// Parameters and request body of inner extractor are not required
+impl<T: ApiExtractor> ApiExtractor for Option<T> {
+    fn operation(op: Operation) -> Operation {
+        let params = op.parameters.len();
+        let has_body = op.body.is_some();
+        let mut op = T::operation(op);
+        for param in &mut op.parameters[params..] {
+            if let Some(param) = param.as_object_mut() {
+                param.insert("required".to_owned(), false.into());
+            }
+        }
+        if !has_body {
+            if let Some((_, ref mut required)) = op.body {
+                *required = false;
+            }
+        }
+        op
+    }
+}
+
+impl ApiExtractor for String {
+    fn operation(op: Operation) -> Operation {
+        op.request_body("text/plain", String::schema())
+    }
+}
+
+impl ApiExtractor for Bytes {
+    fn operation(op: Operation) -> Operation {
+        op.request_body(
+            "application/octet-stream",
+            primitive("string", Some("binary")),
+        )
+    }
+}
+
+impl<S> ApiExtractor for HttpRequest<S> {
+    fn operation(op: Operation) -> Operation {
+        op
+    }
+}
+
+impl<S> ApiExtractor for State<S> {
+    fn operation(op: Operation) -> Operation {
+        op
+    }
+}
+
+macro_rules! tuple_api_extractor ({$($T:ident),+} => {
+    impl<$($T: ApiExtractor),+> ApiExtractor for ($($T,)+) {
+        fn operation(op: Operation) -> Operation {
+            $(let op = $T::operation(op);)+
+            op
+        }
+    }
+});
+
+tuple_api_extractor!(A);
+tuple_api_extractor!(A, B);
+tuple_api_extractor!(A, B, C);
+tuple_api_extractor!(A, B, C, D);
+tuple_api_extractor!(A, B, C, D, E);
+tuple_api_extractor!(A, B, C, D, E, F);
+tuple_api_extractor!(A, B, C, D, E, F, G);
+tuple_api_extractor!(A, B, C, D, E, F, G, H);
+tuple_api_extractor!(A, B, C, D, E, F, G, H, I);
+tuple_api_extractor!(A, B, C, D, E, F, G, H, I, J);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::Method;
+    use header::ContentType;
+
+    struct User;
+
+    impl Schema for User {
+        fn schema() -> Value {
+            ObjectSchema::new()
+                .required("name", String::schema())
+                .optional("age", u32::schema())
+                .into_value()
+        }
+    }
+
+    struct Info;
+
+    impl Schema for Info {
+        fn schema() -> Value {
+            ObjectSchema::new()
+                .required("id", u64::schema())
+                .into_value()
+        }
+    }
+
+    #[test]
+    fn test_path_template() {
+        assert_eq!(
+            path_template("/app/user/{id:\\d+}/{name}"),
+            (
+                "/app/user/{id}/{name}".to_owned(),
+                vec!["id".to_owned(), "name".to_owned()]
+            )
+        );
+        assert_eq!(path_template("/index.html"), ("/index.html".to_owned(), vec![]));
+        assert_eq!(
+            path_template("/files/{tail}*"),
+            ("/files/{tail}".to_owned(), vec!["tail".to_owned()])
+        );
+        assert_eq!(
+            path_template("/archive/{year:\\d+}?/{month}?"),
+            (
+                "/archive/{year}/{month}".to_owned(),
+                vec!["year".to_owned(), "month".to_owned()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_operation() {
+        let op = Operation::new()
+            .summary("Update")
+            .tag("users")
+            .request::<(Path<Info>, Query<User>, Option<Json<User>>)>()
+            .request::<Option<Header<ContentType>>>()
+            .response::<User>(StatusCode::OK, "User")
+            .status(StatusCode::NOT_FOUND, "Not found");
+        let value = op.to_value(&["id".to_owned(), "tail".to_owned()]);
+
+        let params = value["parameters"].as_array().unwrap();
+        let params: Vec<_> = params
+            .iter()
+            .map(|p| {
+                (
+                    p["name"].as_str().unwrap(),
+                    p["in"].as_str().unwrap(),
+                    p["required"].as_bool().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                ("id", "path", true),
+                ("tail", "path", true),
+                ("age", "query", false),
+                ("name", "query", true),
+                ("content-type", "header", false),
+            ]
+        );
+        assert_eq!(value["parameters"][0]["schema"], u64::schema());
+        assert_eq!(value["parameters"][1]["schema"], String::schema());
+
+        assert_eq!(value["requestBody"]["required"], false);
+        assert_eq!(
+            value["requestBody"]["content"]["application/json"]["schema"],
+            User::schema()
+        );
+        assert_eq!(value["responses"]["200"]["description"], "User");
+        assert_eq!(
+            value["responses"]["200"]["content"]["application/json"]["schema"],
+            User::schema()
+        );
+        assert_eq!(value["responses"]["404"]["description"], "Not found");
+        assert_eq!(value["summary"], "Update");
+        assert_eq!(value["tags"][0], "users");
+
+        let value = Operation::new().to_value(&[]);
+        assert!(value.get("parameters").is_none());
+        assert_eq!(
+            value["responses"]["default"]["description"],
+            "Default response"
+        );
+    }
+
+    #[test]
+    fn test_document() {
+        let op = Operation::new().request::<Path<u64>>();
+        let routes = vec![
+            RouteInfo::new(
+                "/app",
+                "/user/{id:\\d+}",
+                "",
+                Some(vec![Method::GET, Method::HEAD]),
+                Vec::new(),
+                Some(op.clone()),
+            ),
+            RouteInfo::new(
+                "/app",
+                "/user/{id:\\d+}",
+                "",
+                Some(vec![Method::DELETE]),
+                Vec::new(),
+                Some(op),
+            ),
+            RouteInfo::new("/app", "/hidden", "", None, Vec::new(), None),
+        ];
+        let doc = OpenApi::new("Test", "1.0")
+            .server("https://example.com")
+            .document(&routes);
+
+        assert_eq!(doc["openapi"], "3.0.0");
+        assert_eq!(doc["info"]["title"], "Test");
+        assert_eq!(doc["servers"][0]["url"], "https://example.com");
+
+        let paths = doc["paths"].as_object().unwrap();
+        assert_eq!(paths.len(), 1);
+        let item = paths["/app/user/{id}"].as_object().unwrap();
+        assert_eq!(
+            item.keys().collect::<Vec<_>>(),
+            vec!["delete", "get", "head"]
+        );
+        assert_eq!(
+            item["get"]["parameters"][0]["schema"],
+            primitive("integer", Some("int64"))
+        );
+    }
+}
@@ -1,9 +1,10 @@
 use http::StatusCode;
+use serde::de::{Deserialize, Deserializer, Error as DeError};
 use smallvec::SmallVec;
 use std;
 use std::borrow::Cow;
-use std::ops::Index;
-use std::path::PathBuf;
+use std::ops::{Deref, Index};
+use std::path::{Path, PathBuf};
 use std::slice::Iter;
 use std::str::FromStr;
 
@@ -34,6 +35,10 @@ impl<'a> Params<'a> {
         self.0.clear();
     }
 
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+
     pub(crate) fn add<N, V>(&mut self, name: N, value: V)
     where
         N: Into<Cow<'a, str>>,
@@ -154,6 +159,63 @@ impl FromParam for PathBuf {
     }
 }
 
+/// Path extracted from a path parameter, usually from a tail parameter.
+///
+/// Value is checked with `PathBuf::from_param()`, `..` segments are
+/// resolved and hidden files are rejected, so it is safe to join it
+/// to a base directory. `PathBuf` is deserialized as is.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::{http, App, Path, PathTail, Result};
+/// use std::path::PathBuf;
+///
+/// fn index(path: Path<PathTail>) -> Result<String> {
+///     Ok(format!("File {:?}", PathBuf::from("./static").join(path.as_path())))
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource(
+///        "/static/{tail}*",                       // <- tail parameter
+///        |r| r.method(http::Method::GET).with(index));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathTail(PathBuf);
+
+impl PathTail {
+    /// Deconstruct to an inner value
+    pub fn into_inner(self) -> PathBuf {
+        self.0
+    }
+}
+
+impl Deref for PathTail {
+    type Target = PathBuf;
+
+    fn deref(&self) -> &PathBuf {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for PathTail {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for PathTail {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        PathBuf::from_param(&value).map(PathTail).map_err(|e| {
+            D::Error::custom(format!("can not parse {:?} to a path: {}", value, e))
+        })
+    }
+}
+
 macro_rules! FROM_STR {
     ($type:ty) => {
         impl FromParam for $type {
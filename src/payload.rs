@@ -309,6 +309,13 @@ where
         }
     }
 
+    /// Size of buffered data
+    #[inline]
+    #[cfg_attr(feature = "cargo-clippy", allow(len_without_is_empty))]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
     /// Get mutable reference to an inner stream.
     pub fn get_mut(&mut self) -> &mut S {
         &mut self.stream
@@ -506,7 +513,6 @@ where
         self.items.push_front(data);
     }
 
-    #[allow(dead_code)]
     pub fn remaining(&mut self) -> Bytes {
         self.items
             .iter_mut()
@@ -11,6 +11,7 @@ use application::Inner;
 use body::{Body, BodyStream};
 use context::{ActorHttpContext, Frame};
 use error::Error;
+use fs::ChunkedReadFile;
 use handler::{Reply, ReplyItem};
 use header::ContentEncoding;
 use httprequest::HttpRequest;
@@ -23,6 +24,7 @@ pub(crate) enum HandlerType {
     Normal(usize),
     Handler(usize),
     Default,
+    Redirect,
 }
 
 pub(crate) trait PipelineHandler<S> {
@@ -445,6 +447,7 @@ enum IOState {
     Response,
     Payload(BodyStream),
     Actor(Box<ActorHttpContext>),
+    File(ChunkedReadFile),
     Done,
 }
 
@@ -515,6 +518,14 @@ impl<S: 'static, H> ProcessResponse<S, H> {
                                     self.iostate = IOState::Actor(ctx);
                                     continue 'inner;
                                 }
+                                Body::File(file) => {
+                                    if io.can_sendfile() {
+                                        self.iostate = IOState::File(file);
+                                    } else {
+                                        self.iostate = IOState::Payload(Box::new(file));
+                                    }
+                                    continue 'inner;
+                                }
                                 _ => (),
                             }
 
@@ -553,6 +564,35 @@ impl<S: 'static, H> ProcessResponse<S, H> {
                                 return Ok(FinishingMiddlewares::init(info, self.resp));
                             }
                         },
+                        IOState::File(mut file) => {
+                            let remaining = file.remaining();
+                            if remaining == 0 {
+                                if let Err(err) = io.write_eof() {
+                                    info.error = Some(err.into());
+                                    return Ok(FinishingMiddlewares::init(
+                                        info,
+                                        self.resp,
+                                    ));
+                                }
+                                break;
+                            }
+                            let result =
+                                io.sendfile(file.file(), file.offset(), remaining);
+                            match result {
+                                Ok((n, result)) => {
+                                    file.advance(n);
+                                    self.iostate = IOState::File(file);
+                                    result
+                                }
+                                Err(err) => {
+                                    info.error = Some(err.into());
+                                    return Ok(FinishingMiddlewares::init(
+                                        info,
+                                        self.resp,
+                                    ));
+                                }
+                            }
+                        }
                         IOState::Actor(mut ctx) => {
                             if info.disconnected.take().is_some() {
                                 ctx.disconnected();
@@ -4,6 +4,7 @@ use http;
 use http::{header, HttpTryFrom};
 use httpmessage::HttpMessage;
 use httprequest::HttpRequest;
+use mime::{self, Mime};
 use std::marker::PhantomData;
 
 /// Trait defines resource route predicate.
@@ -13,6 +14,76 @@ use std::marker::PhantomData;
 pub trait Predicate<S> {
     /// Check if request matches predicate
     fn check(&self, &mut HttpRequest<S>) -> bool;
+
+    /// Http methods that could match predicate, `None` if predicate
+    /// does not check request's method.
+    ///
+    /// Resource uses it for `Allow` header of `405 Method Not Allowed`
+    /// and `OPTIONS` responses.
+    fn methods(&self) -> Option<Vec<http::Method>> {
+        None
+    }
+
+    /// Human readable description of predicate, used for route tables.
+    fn describe(&self) -> String {
+        "custom".to_owned()
+    }
+}
+
+/// Descriptions of predicates, joined with separator
+fn describe_all<S>(preds: &[Box<Predicate<S>>], sep: &str) -> String {
+    preds
+        .iter()
+        .map(|pred| pred.describe())
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+/// Check all predicates, params captured by predicates are dropped
+/// if any of them does not match
+pub(crate) fn check_all<S: 'static>(
+    preds: &[Box<Predicate<S>>], req: &mut HttpRequest<S>
+) -> bool {
+    let len = req.match_info().len();
+    for pred in preds {
+        if !pred.check(req) {
+            req.match_info_mut().truncate(len);
+            return false;
+        }
+    }
+    true
+}
+
+/// Methods that could match all of predicates
+pub(crate) fn all_methods<S>(preds: &[Box<Predicate<S>>]) -> Option<Vec<http::Method>> {
+    let mut result: Option<Vec<http::Method>> = None;
+    for pred in preds {
+        if let Some(methods) = pred.methods() {
+            result = Some(match result {
+                Some(prev) => prev.into_iter()
+                    .filter(|method| methods.contains(method))
+                    .collect(),
+                None => methods,
+            });
+        }
+    }
+    result
+}
+
+/// Methods that could match any of predicates
+pub(crate) fn any_methods<S>(preds: &[Box<Predicate<S>>]) -> Option<Vec<http::Method>> {
+    let mut result = Vec::new();
+    for pred in preds {
+        match pred.methods() {
+            Some(methods) => for method in methods {
+                if !result.contains(&method) {
+                    result.push(method);
+                }
+            },
+            None => return None,
+        }
+    }
+    Some(result)
 }
 
 /// Return predicate that matches if any of supplied predicate matches.
@@ -45,13 +116,23 @@ impl<S> AnyPredicate<S> {
 
 impl<S: 'static> Predicate<S> for AnyPredicate<S> {
     fn check(&self, req: &mut HttpRequest<S>) -> bool {
+        let len = req.match_info().len();
         for p in &self.0 {
             if p.check(req) {
                 return true;
             }
+            req.match_info_mut().truncate(len);
         }
         false
     }
+
+    fn methods(&self) -> Option<Vec<http::Method>> {
+        any_methods(&self.0)
+    }
+
+    fn describe(&self) -> String {
+        format!("({})", describe_all(&self.0, " or "))
+    }
 }
 
 /// Return predicate that matches if all of supplied predicate matches.
@@ -85,12 +166,15 @@ impl<S> AllPredicate<S> {
 
 impl<S: 'static> Predicate<S> for AllPredicate<S> {
     fn check(&self, req: &mut HttpRequest<S>) -> bool {
-        for p in &self.0 {
-            if !p.check(req) {
-                return false;
-            }
-        }
-        true
+        check_all(&self.0, req)
+    }
+
+    fn methods(&self) -> Option<Vec<http::Method>> {
+        all_methods(&self.0)
+    }
+
+    fn describe(&self) -> String {
+        format!("({})", describe_all(&self.0, " and "))
     }
 }
 
@@ -104,7 +188,14 @@ pub struct NotPredicate<S>(Box<Predicate<S>>);
 
 impl<S: 'static> Predicate<S> for NotPredicate<S> {
     fn check(&self, req: &mut HttpRequest<S>) -> bool {
-        !self.0.check(req)
+        let len = req.match_info().len();
+        let res = self.0.check(req);
+        req.match_info_mut().truncate(len);
+        !res
+    }
+
+    fn describe(&self) -> String {
+        format!("not {}", self.0.describe())
     }
 }
 
@@ -116,6 +207,14 @@ impl<S: 'static> Predicate<S> for MethodPredicate<S> {
     fn check(&self, req: &mut HttpRequest<S>) -> bool {
         *req.method() == self.0
     }
+
+    fn methods(&self) -> Option<Vec<http::Method>> {
+        Some(vec![self.0.clone()])
+    }
+
+    fn describe(&self) -> String {
+        self.0.to_string()
+    }
 }
 
 /// Predicate to match *GET* http method
@@ -194,6 +293,258 @@ impl<S: 'static> Predicate<S> for HeaderPredicate<S> {
         }
         false
     }
+
+    fn describe(&self) -> String {
+        format!(
+            "header({}: {})",
+            self.0,
+            self.1.to_str().unwrap_or("<binary>")
+        )
+    }
+}
+
+/// Return predicate that matches if request's host matches pattern.
+///
+/// Port is ignored and comparison is case insensitive. Pattern could
+/// start with `*.` that matches any subdomain, or with `{name}.` that
+/// matches any subdomain and stores it to request's `match_info()` under
+/// `name` key. Captured subdomain is dropped if other predicates of
+/// the route do not match.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::{pred, App, HttpRequest, HttpResponse};
+///
+/// fn index(req: HttpRequest) -> String {
+///     format!("Welcome {}!", &req.match_info()["user"])
+/// }
+///
+/// fn main() {
+///     App::new()
+///         .resource("/", |r| {
+///             r.route().filter(pred::Host("{user}.example.com")).f(index);
+///             r.route().filter(pred::Host("www.example.com")).f(|_| "Welcome!");
+///         });
+/// }
+/// ```
+pub fn Host<S: 'static, H: AsRef<str>>(host: H) -> HostPredicate<S> {
+    let host = host.as_ref().trim_right_matches('.');
+    let (capture, domain) = if host.starts_with("*.") {
+        (Some(None), &host[2..])
+    } else if let (true, Some(end)) = (host.starts_with('{'), host.find("}.")) {
+        (Some(Some(host[1..end].to_owned())), &host[end + 2..])
+    } else {
+        (None, host)
+    };
+    HostPredicate {
+        capture,
+        domain: domain.to_lowercase(),
+        _s: PhantomData,
+    }
+}
+
+#[doc(hidden)]
+pub struct HostPredicate<S> {
+    domain: String,
+    capture: Option<Option<String>>,
+    _s: PhantomData<S>,
+}
+
+/// Strip port and trailing dot from host name
+fn host_name(host: &str) -> &str {
+    let host = if host.starts_with('[') {
+        match host.find(']') {
+            Some(pos) => &host[..pos + 1],
+            None => host,
+        }
+    } else {
+        match host.rfind(':') {
+            Some(pos) => &host[..pos],
+            None => host,
+        }
+    };
+    host.trim_right_matches('.')
+}
+
+impl<S: 'static> Predicate<S> for HostPredicate<S> {
+    fn check(&self, req: &mut HttpRequest<S>) -> bool {
+        let host = host_name(req.connection_info().host()).to_lowercase();
+        let capture = match self.capture {
+            None => return host == self.domain,
+            Some(ref capture) => capture,
+        };
+
+        let sub_len = match host.len().checked_sub(self.domain.len() + 1) {
+            Some(len) if len > 0 => len,
+            _ => return false,
+        };
+        if !host.ends_with(&self.domain) || host.as_bytes()[sub_len] != b'.' {
+            return false;
+        }
+        if let Some(ref name) = *capture {
+            let sub = host[..sub_len].to_owned();
+            req.match_info_mut().add(name.clone(), sub);
+        }
+        true
+    }
+
+    fn describe(&self) -> String {
+        match self.capture {
+            None => format!("host({})", self.domain),
+            Some(None) => format!("host(*.{})", self.domain),
+            Some(Some(ref name)) => format!("host({{{}}}.{})", name, self.domain),
+        }
+    }
+}
+
+/// Return predicate that matches if request's scheme is equal to
+/// specified scheme, i.e. `http` or `https`.
+///
+/// Scheme is determined with `HttpRequest::connection_info()`, so proxy
+/// headers are taken into account.
+pub fn Scheme<S: 'static>(scheme: &str) -> SchemePredicate<S> {
+    SchemePredicate(scheme.to_lowercase(), PhantomData)
+}
+
+#[doc(hidden)]
+pub struct SchemePredicate<S>(String, PhantomData<S>);
+
+impl<S: 'static> Predicate<S> for SchemePredicate<S> {
+    fn check(&self, req: &mut HttpRequest<S>) -> bool {
+        req.connection_info().scheme().to_lowercase() == self.0
+    }
+
+    fn describe(&self) -> String {
+        format!("scheme({})", self.0)
+    }
+}
+
+/// Return predicate that matches if request's query contains
+/// specified parameter. Parameter's value could be checked with
+/// `QueryPredicate::value()` method.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::{pred, App, HttpResponse};
+///
+/// fn main() {
+///     App::new()
+///         .resource("/index.html", |r| r.route()
+///             .filter(pred::Query("format").value("xml"))
+///             .f(|_| HttpResponse::NotImplemented()));
+/// }
+/// ```
+pub fn Query<S: 'static>(name: &'static str) -> QueryPredicate<S> {
+    QueryPredicate {
+        name,
+        value: None,
+        _s: PhantomData,
+    }
+}
+
+#[doc(hidden)]
+pub struct QueryPredicate<S> {
+    name: &'static str,
+    value: Option<&'static str>,
+    _s: PhantomData<S>,
+}
+
+impl<S> QueryPredicate<S> {
+    /// Match only if parameter is equal to value
+    pub fn value(mut self, value: &'static str) -> Self {
+        self.value = Some(value);
+        self
+    }
+}
+
+impl<S: 'static> Predicate<S> for QueryPredicate<S> {
+    fn check(&self, req: &mut HttpRequest<S>) -> bool {
+        match req.query().get(self.name) {
+            Some(val) => self.value.map(|v| v == val).unwrap_or(true),
+            None => false,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self.value {
+            Some(value) => format!("query({}={})", self.name, value),
+            None => format!("query({})", self.name),
+        }
+    }
+}
+
+/// Return predicate that matches if request's content type matches
+/// specified mime type. Type and subtype could be a `*` wildcard,
+/// parameters are ignored.
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::{pred, App, HttpResponse};
+///
+/// fn main() {
+///     App::new()
+///         .resource("/upload", |r| {
+///             r.route()
+///                 .filter(pred::ContentType("image/*"))
+///                 .f(|_| HttpResponse::Ok());
+///             r.f(|_| HttpResponse::UnsupportedMediaType())
+///         });
+/// }
+/// ```
+pub fn ContentType<S: 'static>(mime: &str) -> ContentTypePredicate<S> {
+    ContentTypePredicate(mime.parse().expect("Invalid mime type"), PhantomData)
+}
+
+#[doc(hidden)]
+pub struct ContentTypePredicate<S>(Mime, PhantomData<S>);
+
+impl<S: 'static> Predicate<S> for ContentTypePredicate<S> {
+    fn check(&self, req: &mut HttpRequest<S>) -> bool {
+        match req.mime_type() {
+            Ok(Some(mime)) => {
+                (self.0.type_() == mime::STAR || self.0.type_() == mime.type_())
+                    && (self.0.subtype() == mime::STAR
+                        || self.0.subtype() == mime.subtype())
+            }
+            _ => false,
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("content-type({})", self.0)
+    }
+}
+
+/// Return predicate that matches if closure returns `true`
+///
+/// ```rust
+/// # extern crate actix_web;
+/// use actix_web::{pred, App, HttpMessage, HttpResponse};
+///
+/// fn main() {
+///     App::new()
+///         .resource("/index.html", |r| r.route()
+///             .filter(pred::Fn(|req| req.cookie("session").is_some()))
+///             .f(|_| HttpResponse::Ok()));
+/// }
+/// ```
+pub fn Fn<S: 'static, F>(f: F) -> FnPredicate<S, F>
+where
+    F: Fn(&HttpRequest<S>) -> bool + 'static,
+{
+    FnPredicate(f, PhantomData)
+}
+
+#[doc(hidden)]
+pub struct FnPredicate<S, F>(F, PhantomData<S>);
+
+impl<S: 'static, F> Predicate<S> for FnPredicate<S, F>
+where
+    F: Fn(&HttpRequest<S>) -> bool + 'static,
+{
+    fn check(&self, req: &mut HttpRequest<S>) -> bool {
+        (self.0)(req)
+    }
 }
 
 #[cfg(test)]
@@ -202,6 +553,7 @@ mod tests {
     use http::header::{self, HeaderMap};
     use http::{Method, Uri, Version};
     use std::str::FromStr;
+    use test::TestRequest;
 
     #[test]
     fn test_header() {
@@ -321,6 +673,45 @@ mod tests {
         assert!(!Trace().check(&mut req));
     }
 
+    #[test]
+    fn test_methods_list() {
+        assert_eq!(Get::<()>().methods(), Some(vec![Method::GET]));
+        assert_eq!(Not(Get::<()>()).methods(), None);
+        assert_eq!(
+            Any(Get::<()>()).or(Post()).or(Get()).methods(),
+            Some(vec![Method::GET, Method::POST])
+        );
+        assert_eq!(Any(Get::<()>()).or(Header("x", "y")).methods(), None);
+        assert_eq!(
+            All(Any(Get::<()>()).or(Post()))
+                .and(Post())
+                .and(Header("x", "y"))
+                .methods(),
+            Some(vec![Method::POST])
+        );
+        assert_eq!(All(Header::<()>("x", "y")).methods(), None);
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(Get::<()>().describe(), "GET");
+        assert_eq!(
+            Any(Get::<()>()).or(Header("x-api", "1")).describe(),
+            "(GET or header(x-api: 1))"
+        );
+        assert_eq!(
+            All(Not(Post::<()>())).and(Query("q").value("1")).describe(),
+            "(not POST and query(q=1))"
+        );
+        assert_eq!(
+            Host::<()>("{name}.example.com").describe(),
+            "host({name}.example.com)"
+        );
+        assert_eq!(Scheme::<()>("HTTPS").describe(), "scheme(https)");
+        assert_eq!(ContentType::<()>("image/*").describe(), "content-type(image/*)");
+        assert_eq!(Fn(|_: &HttpRequest| true).describe(), "custom");
+    }
+
     #[test]
     fn test_preds() {
         let mut r = HttpRequest::new(
@@ -340,4 +731,61 @@ mod tests {
         assert!(Any(Get()).or(Trace()).check(&mut r));
         assert!(!Any(Get()).or(Get()).check(&mut r));
     }
+
+    #[test]
+    fn test_host() {
+        let mut req = TestRequest::with_header("host", "Sub.Example.com:8080").finish();
+        assert!(Host("sub.example.com").check(&mut req));
+        assert!(Host("*.example.com").check(&mut req));
+        assert!(!Host("example.com").check(&mut req));
+        assert!(!Host("*.sub.example.com").check(&mut req));
+        assert!(!Host("*.ample.com").check(&mut req));
+
+        assert!(Host("{name}.example.com").check(&mut req));
+        assert_eq!(req.match_info().get("name"), Some("sub"));
+
+        let mut req = TestRequest::with_header("host", "sub.example.com").finish();
+        let preds: Vec<Box<Predicate<()>>> =
+            vec![Box::new(Host("{name}.example.com")), Box::new(Post())];
+        assert!(!check_all(&preds, &mut req));
+        assert!(!Not(Host("{name}.example.com")).check(&mut req));
+        assert!(Any(All(Host("{name}.example.com")).and(Post()))
+            .or(Get())
+            .check(&mut req));
+        assert_eq!(req.match_info().len(), 0);
+
+        let mut req = TestRequest::with_header("host", "example.com").finish();
+        assert!(!Host("*.example.com").check(&mut req));
+        assert!(Host("example.com.").check(&mut req));
+        assert_eq!(host_name("[::1]:8080"), "[::1]");
+    }
+
+    #[test]
+    fn test_scheme_and_query() {
+        let mut req = TestRequest::with_uri("/?format=xml&debug")
+            .header("x-forwarded-proto", "https")
+            .finish();
+        assert!(Scheme("https").check(&mut req));
+        assert!(!Scheme("http").check(&mut req));
+
+        assert!(Query("format").check(&mut req));
+        assert!(Query("debug").check(&mut req));
+        assert!(Query("format").value("xml").check(&mut req));
+        assert!(!Query("format").value("json").check(&mut req));
+        assert!(!Query("other").check(&mut req));
+    }
+
+    #[test]
+    fn test_content_type_and_fn() {
+        let mut req =
+            TestRequest::with_header("content-type", "image/png; q=1").finish();
+        assert!(ContentType("image/png").check(&mut req));
+        assert!(ContentType("image/*").check(&mut req));
+        assert!(ContentType("*/*").check(&mut req));
+        assert!(!ContentType("text/*").check(&mut req));
+        assert!(!ContentType("*/*").check(&mut HttpRequest::default()));
+
+        assert!(Fn(|req: &HttpRequest| req.path() == "/").check(&mut req));
+        assert!(!Fn(|req: &HttpRequest| req.path() == "/index").check(&mut req));
+    }
 }
@@ -5,11 +5,13 @@ use http::{Method, StatusCode};
 use smallvec::SmallVec;
 
 use handler::{FromRequest, Handler, Reply, Responder};
+use header::Allow;
 use httprequest::HttpRequest;
 use httpresponse::HttpResponse;
 use middleware::Middleware;
 use pred;
 use route::Route;
+use router::{Resource, RouteInfo};
 
 /// *Resource* is an entry in route table which corresponds to requested URL.
 ///
@@ -21,6 +23,12 @@ use route::Route;
 /// and check all predicates for specific route, if request matches all
 /// predicates route route considered matched and route handler get called.
 ///
+/// If path matches resource but none of routes accepts request's method,
+/// `405 Method Not Allowed` response with `Allow` header is returned.
+/// `OPTIONS` requests are answered automatically, unless resource has
+/// a route for `OPTIONS` method. `HEAD` requests are handled by `GET`
+/// routes if resource has no route for `HEAD` method.
+///
 /// ```rust
 /// # extern crate actix_web;
 /// use actix_web::{App, HttpResponse, http};
@@ -193,18 +201,86 @@ impl<S: 'static> ResourceHandler<S> {
             .push(Box::new(mw));
     }
 
+    /// Methods accepted by resource's routes, `None` if resource
+    /// has no routes or some route accepts any method
+    fn allowed_methods(&self) -> Option<Vec<Method>> {
+        if self.routes.is_empty() {
+            return None;
+        }
+        let mut allowed = Vec::new();
+        for route in &self.routes {
+            match route.methods() {
+                Some(methods) => for method in methods {
+                    if !allowed.contains(&method) {
+                        allowed.push(method);
+                    }
+                },
+                None => return None,
+            }
+        }
+        if allowed.contains(&Method::GET) && !allowed.contains(&Method::HEAD) {
+            allowed.push(Method::HEAD);
+        }
+        if !allowed.contains(&Method::OPTIONS) {
+            allowed.push(Method::OPTIONS);
+        }
+        Some(allowed)
+    }
+
+    /// Route table entries of resource's routes
+    pub(crate) fn routes_info(
+        &self, prefix: &str, pattern: &Resource
+    ) -> Vec<RouteInfo> {
+        self.routes
+            .iter()
+            .map(|route| {
+                RouteInfo::new(
+                    prefix,
+                    pattern.pattern(),
+                    pattern.name(),
+                    route.methods(),
+                    route.describe(),
+                    route.operation().cloned(),
+                )
+            })
+            .collect()
+    }
+
     pub(crate) fn handle(
         &mut self, mut req: HttpRequest<S>, default: Option<&mut ResourceHandler<S>>
     ) -> Reply {
-        for route in &mut self.routes {
-            if route.check(&mut req) {
-                return if self.middlewares.is_empty() {
-                    route.handle(req)
-                } else {
-                    route.compose(req, Rc::clone(&self.middlewares))
-                };
+        let mut idx = self.routes.iter().position(|route| route.check(&mut req));
+
+        // `HEAD` is served by `GET` routes, body is dropped by response writer
+        if idx.is_none() && *req.method() == Method::HEAD {
+            req.as_mut().method = Method::GET;
+            idx = self.routes.iter().position(|route| route.check(&mut req));
+            req.as_mut().method = Method::HEAD;
+        }
+
+        if let Some(idx) = idx {
+            let route = &mut self.routes[idx];
+            return if self.middlewares.is_empty() {
+                route.handle(req)
+            } else {
+                route.compose(req, Rc::clone(&self.middlewares))
+            };
+        }
+
+        // resource does not accept request's method,
+        // `OPTIONS` is answered automatically
+        if let Some(allowed) = self.allowed_methods() {
+            if *req.method() == Method::OPTIONS {
+                return Reply::response(HttpResponse::Ok().set(Allow(allowed)).finish());
+            } else if !allowed.contains(req.method()) {
+                return Reply::response(
+                    HttpResponse::MethodNotAllowed()
+                        .set(Allow(allowed))
+                        .finish(),
+                );
             }
         }
+
         if let Some(resource) = default {
             resource.handle(req, None)
         } else {
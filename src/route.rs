@@ -6,12 +6,13 @@ use std::rc::Rc;
 use error::Error;
 use handler::{AsyncHandler, FromRequest, Handler, Reply, ReplyItem, Responder,
               RouteHandler, WrapHandler};
-use http::StatusCode;
+use http::{Method, StatusCode};
 use httprequest::HttpRequest;
 use httpresponse::HttpResponse;
 use middleware::{Middleware, Response as MiddlewareResponse,
                  Started as MiddlewareStarted};
-use pred::Predicate;
+use openapi::Operation;
+use pred::{self, Predicate};
 use with::{ExtractorConfig, With, With2, With3};
 
 /// Resource route definition
@@ -21,6 +22,7 @@ use with::{ExtractorConfig, With, With2, With3};
 pub struct Route<S> {
     preds: Vec<Box<Predicate<S>>>,
     handler: InnerHandler<S>,
+    operation: Option<Operation>,
 }
 
 impl<S: 'static> Default for Route<S> {
@@ -28,6 +30,7 @@ impl<S: 'static> Default for Route<S> {
         Route {
             preds: Vec::new(),
             handler: InnerHandler::new(|_| HttpResponse::new(StatusCode::NOT_FOUND)),
+            operation: None,
         }
     }
 }
@@ -35,12 +38,33 @@ impl<S: 'static> Default for Route<S> {
 impl<S: 'static> Route<S> {
     #[inline]
     pub(crate) fn check(&self, req: &mut HttpRequest<S>) -> bool {
-        for pred in &self.preds {
-            if !pred.check(req) {
-                return false;
-            }
-        }
-        true
+        pred::check_all(&self.preds, req)
+    }
+
+    /// Methods accepted by route, `None` if route accepts any method
+    pub(crate) fn methods(&self) -> Option<Vec<Method>> {
+        pred::all_methods(&self.preds)
+    }
+
+    /// Descriptions of route predicates, plain method predicates
+    /// are skipped, they are reported by `methods()`
+    pub(crate) fn describe(&self) -> Vec<String> {
+        self.preds
+            .iter()
+            .map(|pred| (pred.methods(), pred.describe()))
+            .filter(|&(ref methods, ref descr)| match *methods {
+                Some(ref methods) => {
+                    methods.len() != 1 || methods[0].as_str() != descr.as_str()
+                }
+                None => true,
+            })
+            .map(|(_, descr)| descr)
+            .collect()
+    }
+
+    /// OpenAPI description of the route
+    pub(crate) fn operation(&self) -> Option<&Operation> {
+        self.operation.as_ref()
     }
 
     #[inline]
@@ -76,6 +100,30 @@ impl<S: 'static> Route<S> {
         self
     }
 
+    /// Set OpenAPI description of the route, see `App::openapi()`.
+    ///
+    /// ```rust
+    /// # extern crate actix_web;
+    /// use actix_web::openapi::Operation;
+    /// use actix_web::{http, App, Path};
+    ///
+    /// fn index(id: Path<u64>) -> String {
+    ///     format!("Item {}", *id)
+    /// }
+    ///
+    /// fn main() {
+    ///     App::new().resource("/item/{id}", |r| {
+    ///         r.get()
+    ///             .doc(Operation::new().summary("Get item").request::<Path<u64>>())
+    ///             .with(index);
+    ///     });
+    /// }
+    /// ```
+    pub fn doc(&mut self, operation: Operation) -> &mut Self {
+        self.operation = Some(operation);
+        self
+    }
+
     /// Set handler object. Usually call to this method is last call
     /// during route configuration, so it does not return reference to self.
     pub fn h<H: Handler<S>>(&mut self, handler: H) {
@@ -128,6 +176,39 @@ impl<S: 'static> Route<S> {
     ///        |r| r.method(http::Method::GET).with(index)); // <- use `with` extractor
     /// }
     /// ```
+    ///
+    /// Tuples of up to 10 extractors are extractors too, so handler could
+    /// accept any number of parameters. Returned config is a tuple of
+    /// extractors' configs.
+    ///
+    /// ```rust
+    /// # extern crate bytes;
+    /// # extern crate actix_web;
+    /// # extern crate futures;
+    /// #[macro_use] extern crate serde_derive;
+    /// use actix_web::{App, Form, HttpRequest, Path, Query, Result, http};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Info {
+    ///     username: String,
+    /// }
+    ///
+    /// fn index(
+    ///     (_req, path, _query, _form):
+    ///         (HttpRequest, Path<Info>, Query<Info>, Form<Info>),
+    /// ) -> Result<String> {
+    ///     Ok(format!("Welcome {}!", path.username))
+    /// }
+    ///
+    /// fn main() {
+    ///     let app = App::new().resource(
+    ///        "/{username}/index.html",
+    ///        |r| {
+    ///            let mut cfg = r.method(http::Method::POST).with(index);
+    ///            cfg.3.limit(4096); // <- change form extractor limit
+    ///        });
+    /// }
+    /// ```
     pub fn with<T, F, R>(&mut self, handler: F) -> ExtractorConfig<S, T>
     where
         F: Fn(T) -> R + 'static,
@@ -139,6 +220,51 @@ impl<S: 'static> Route<S> {
         cfg
     }
 
+    /// Set async handler function, use request extractor for paramters.
+    ///
+    /// ```rust
+    /// # extern crate bytes;
+    /// # extern crate actix_web;
+    /// # extern crate futures;
+    /// #[macro_use] extern crate serde_derive;
+    /// use actix_web::{App, Error, Path, Query, http};
+    /// use futures::Future;
+    /// use futures::future::ok;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Info {
+    ///     username: String,
+    /// }
+    ///
+    /// /// extract path and query info using serde
+    /// fn index(
+    ///     (path, query): (Path<Info>, Query<Info>)
+    /// ) -> Box<Future<Item = String, Error = Error>> {
+    ///     Box::new(ok(format!("Welcome {}!", path.username)))
+    /// }
+    ///
+    /// fn main() {
+    ///     let app = App::new().resource(
+    ///        "/{username}/index.html",
+    ///        |r| r.method(http::Method::GET).with_async(index));
+    /// }
+    /// ```
+    pub fn with_async<T, F, R, I, E>(&mut self, handler: F) -> ExtractorConfig<S, T>
+    where
+        F: Fn(T) -> R + 'static,
+        R: Future<Item = I, Error = E> + 'static,
+        I: Responder + 'static,
+        E: Into<Error> + 'static,
+        T: FromRequest<S> + 'static,
+    {
+        let cfg = ExtractorConfig::default();
+        self.h(With::new(
+            move |item| -> Box<Future<Item = I, Error = E>> { Box::new(handler(item)) },
+            Clone::clone(&cfg),
+        ));
+        cfg
+    }
+
     /// Set handler function, use request extractor for both paramters.
     ///
     /// ```rust
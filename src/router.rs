@@ -1,13 +1,20 @@
+use std::borrow::Cow;
+use std::cmp;
 use std::collections::HashMap;
+use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::mem;
 use std::rc::Rc;
 
+use mime::Mime;
 use percent_encoding::percent_decode;
 use regex::{escape, Regex};
 
-use error::UrlGenerationError;
+use error::{Error, UrlGenerationError};
+use http::Method;
 use httprequest::HttpRequest;
+use negotiate::ResponseFormat;
+use openapi::Operation;
 use param::Params;
 use resource::ResourceHandler;
 use server::ServerSettings;
@@ -20,7 +27,11 @@ struct Inner {
     prefix_len: usize,
     named: HashMap<String, (Resource, bool)>,
     patterns: Vec<Resource>,
+    tree: Tree,
     srv: ServerSettings,
+    error_handler: Option<Rc<Fn(Error, HttpRequest) -> Error>>,
+    formats: Vec<(Mime, ResponseFormat)>,
+    routes: Vec<RouteInfo>,
 }
 
 impl Router {
@@ -33,6 +44,7 @@ impl Router {
         let mut named = HashMap::new();
         let mut patterns = Vec::new();
         let mut resources = Vec::new();
+        let mut tree = Tree::new();
 
         for (pattern, resource) in map {
             if !pattern.name().is_empty() {
@@ -41,6 +53,7 @@ impl Router {
             }
 
             if let Some(resource) = resource {
+                tree.insert(&pattern.segments, patterns.len());
                 patterns.push(pattern);
                 resources.push(resource);
             }
@@ -53,7 +66,11 @@ impl Router {
                 prefix_len,
                 named,
                 patterns,
+                tree,
                 srv: settings,
+                error_handler: None,
+                formats: Vec::new(),
+                routes: Vec::new(),
             })),
             resources,
         )
@@ -71,6 +88,54 @@ impl Router {
         &self.0.srv
     }
 
+    /// Set application's default extractor error handler.
+    /// Could be called only before router is shared.
+    pub(crate) fn set_error_handler(
+        &mut self, handler: Option<Rc<Fn(Error, HttpRequest) -> Error>>
+    ) {
+        Rc::get_mut(&mut self.0)
+            .expect("Router is shared")
+            .error_handler = handler;
+    }
+
+    /// Application's default extractor error handler
+    pub(crate) fn error_handler(&self) -> Option<&Rc<Fn(Error, HttpRequest) -> Error>> {
+        self.0.error_handler.as_ref()
+    }
+
+    /// Set application's response formats.
+    /// Could be called only before router is shared.
+    pub(crate) fn set_response_formats(&mut self, formats: Vec<(Mime, ResponseFormat)>) {
+        Rc::get_mut(&mut self.0)
+            .expect("Router is shared")
+            .formats = formats;
+    }
+
+    /// Response formats of `Negotiated` responder, built-in formats
+    /// and formats registered with `App::response_format()`
+    pub(crate) fn response_formats(&self) -> &[(Mime, ResponseFormat)] {
+        &self.0.formats
+    }
+
+    /// Set application's route table.
+    /// Could be called only before router is shared.
+    pub(crate) fn set_routes(&mut self, routes: Vec<RouteInfo>) {
+        Rc::get_mut(&mut self.0)
+            .expect("Router is shared")
+            .routes = routes;
+    }
+
+    /// Application's route table, in order of registration
+    pub fn routes(&self) -> &[RouteInfo] {
+        &self.0.routes
+    }
+
+    /// Resources that could never be matched, because earlier
+    /// resource matches every path they match.
+    pub fn conflicts(&self) -> Vec<RouteConflict> {
+        find_conflicts(&self.0.patterns)
+    }
+
     pub(crate) fn get_resource(&self, idx: usize) -> &Resource {
         &self.0.patterns[idx]
     }
@@ -86,13 +151,22 @@ impl Router {
             .decode_utf8()
             .unwrap();
 
-        for (idx, pattern) in self.0.patterns.iter().enumerate() {
-            if pattern.match_with_params(p.as_ref(), req.match_info_mut()) {
-                req.set_resource(idx);
-                return Some(idx);
+        let (idx, captures) = match self.0.tree.recognize(p.as_ref()) {
+            Some(item) => item,
+            None => return None,
+        };
+        {
+            let params = req.match_info_mut();
+            for (name, value) in captures {
+                if let Cow::Owned(_) = p {
+                    params.add(name, value.to_owned());
+                } else {
+                    params.add(name, value);
+                }
             }
         }
-        None
+        req.set_resource(idx);
+        Some(idx)
     }
 
     /// Check if application contains matching route.
@@ -103,13 +177,7 @@ impl Router {
     /// would return `false`.
     pub fn has_route(&self, path: &str) -> bool {
         let path = if path.is_empty() { "/" } else { path };
-
-        for pattern in &self.0.patterns {
-            if pattern.is_match(path) {
-                return true;
-            }
-        }
-        false
+        self.0.tree.recognize(path).is_some()
     }
 
     /// Build named resource path.
@@ -141,6 +209,7 @@ impl Clone for Router {
 enum PatternElement {
     Str(String),
     Var(String),
+    Optional(String),
 }
 
 #[derive(Clone, Debug)]
@@ -170,6 +239,7 @@ pub struct Resource {
     name: String,
     pattern: String,
     elements: Vec<PatternElement>,
+    segments: Vec<Segment>,
 }
 
 impl Resource {
@@ -197,12 +267,13 @@ impl Resource {
             name: "".to_owned(),
             pattern: "".to_owned(),
             elements: Vec::new(),
+            segments: Vec::new(),
         }
     }
 
     /// Parse path pattern and create new `Resource` instance with custom prefix
     pub fn with_prefix(name: &str, path: &str, prefix: &str) -> Self {
-        let (pattern, elements, is_dynamic) = Resource::parse(path, prefix);
+        let (pattern, elements, segments, is_dynamic) = Resource::parse(path, prefix);
 
         let tp = if is_dynamic {
             let re = match Regex::new(&pattern) {
@@ -220,6 +291,7 @@ impl Resource {
         Resource {
             tp,
             elements,
+            segments,
             name: name.into(),
             rtp: ResourceType::Normal,
             pattern: path.to_owned(),
@@ -255,13 +327,10 @@ impl Resource {
             PatternType::Static(ref s) => s == path,
             PatternType::Dynamic(ref re, ref names) => {
                 if let Some(captures) = re.captures(path) {
-                    let mut idx = 0;
-                    for capture in captures.iter() {
-                        if let Some(ref m) = capture {
-                            if idx != 0 {
-                                params.add(names[idx - 1].as_str(), m.as_str());
-                            }
-                            idx += 1;
+                    for name in names {
+                        // missing optional parameters are skipped
+                        if let Some(m) = captures.name(name) {
+                            params.add(name.as_str(), m.as_str());
                         }
                     }
                     true
@@ -296,12 +365,25 @@ impl Resource {
                         return Err(UrlGenerationError::NotEnoughElements);
                     }
                 }
+                PatternElement::Optional(_) => {
+                    // rest of optional segments are omitted as well
+                    if let Some(val) = iter.next() {
+                        if !path.ends_with('/') {
+                            path.push('/');
+                        }
+                        path.push_str(val.as_ref())
+                    } else {
+                        break;
+                    }
+                }
             }
         }
         Ok(path)
     }
 
-    fn parse(pattern: &str, prefix: &str) -> (String, Vec<PatternElement>, bool) {
+    fn parse(
+        pattern: &str, prefix: &str
+    ) -> (String, Vec<PatternElement>, Vec<Segment>, bool) {
         const DEFAULT_PATTERN: &str = "[^/]+";
 
         let mut re1 = String::from("^") + prefix;
@@ -312,19 +394,89 @@ impl Resource {
         let mut param_name = String::new();
         let mut param_pattern = String::from(DEFAULT_PATTERN);
         let mut is_dynamic = false;
+        let mut optional = false;
+        let mut skip = false;
         let mut elems = Vec::new();
+        let mut segments = Vec::new();
+        let mut segment = String::from(prefix);
 
-        for (index, ch) in pattern.chars().enumerate() {
+        for (index, ch) in pattern.char_indices() {
             // All routes must have a leading slash so its optional to have one
             if index == 0 && ch == '/' {
                 continue;
             }
+            // Parameter modifier, `*` or `?`, is already handled
+            if skip {
+                skip = false;
+                continue;
+            }
 
             if in_param {
                 // In parameter segment: `{....}`
                 if ch == '}' {
-                    elems.push(PatternElement::Var(param_name.clone()));
-                    re1.push_str(&format!(r"(?P<{}>{})", &param_name, &param_pattern));
+                    let param = if in_param_pattern {
+                        Segment::Custom(param_name.clone(), param_pattern.clone())
+                    } else {
+                        Segment::Param(param_name.clone())
+                    };
+                    let re = format!(r"(?P<{}>{})", &param_name, &param_pattern);
+
+                    match pattern[index + 1..].chars().next() {
+                        // Tail parameter: `{name}*`
+                        Some('*') => {
+                            if in_param_pattern || index + 2 != pattern.len() {
+                                panic!(
+                                    "Wrong path pattern: \"{}\" tail parameter \
+                                     has to be at the end and can not have pattern",
+                                    pattern
+                                );
+                            }
+                            elems.push(PatternElement::Var(param_name.clone()));
+                            re1.push_str(&format!(r"(?P<{}>.*)", &param_name));
+                            segments.push(Segment::Tail(param_name.clone()));
+                            skip = true;
+                        }
+                        // Optional segment: `/{name}?`
+                        Some('?') => {
+                            match segments.last() {
+                                Some(&Segment::Static(ref s)) if s.ends_with('/') => (),
+                                _ => panic!(
+                                    "Wrong path pattern: \"{}\" optional parameter \
+                                     has to be a whole segment",
+                                    pattern
+                                ),
+                            }
+                            if let Some(&mut PatternElement::Str(ref mut s)) =
+                                elems.last_mut()
+                            {
+                                if s.ends_with('/') {
+                                    s.pop();
+                                }
+                            }
+                            elems.push(PatternElement::Optional(param_name.clone()));
+                            if re1 == "^/" {
+                                re1.push_str(&format!("{}?", re));
+                            } else {
+                                re1.pop();
+                                re1.push_str(&format!("(?:/{})?", re));
+                            }
+                            segments.push(Segment::Optional(Box::new(param)));
+                            optional = true;
+                            skip = true;
+                        }
+                        _ => {
+                            if optional {
+                                panic!(
+                                    "Wrong path pattern: \"{}\" only optional \
+                                     parameters could follow optional parameter",
+                                    pattern
+                                );
+                            }
+                            elems.push(PatternElement::Var(param_name.clone()));
+                            re1.push_str(&re);
+                            segments.push(param);
+                        }
+                    }
 
                     param_name.clear();
                     param_pattern = String::from(DEFAULT_PATTERN);
@@ -348,12 +500,39 @@ impl Resource {
                 is_dynamic = true;
                 elems.push(PatternElement::Str(el.clone()));
                 el.clear();
+                if !segment.is_empty() {
+                    segments.push(Segment::Static(mem::replace(
+                        &mut segment,
+                        String::new(),
+                    )));
+                }
             } else {
+                if optional && (ch != '/' || !el.is_empty()) {
+                    panic!(
+                        "Wrong path pattern: \"{}\" only optional \
+                         parameters could follow optional parameter",
+                        pattern
+                    );
+                }
                 re1.push_str(escape(&ch.to_string()).as_str());
                 re2.push(ch);
                 el.push(ch);
+                segment.push(ch);
             }
         }
+        if optional && !el.is_empty() {
+            panic!(
+                "Wrong path pattern: \"{}\" only optional \
+                 parameters could follow optional parameter",
+                pattern
+            );
+        }
+        if !el.is_empty() {
+            elems.push(PatternElement::Str(el));
+        }
+        if !segment.is_empty() {
+            segments.push(Segment::Static(segment));
+        }
 
         let re = if is_dynamic {
             re1.push('$');
@@ -361,10 +540,688 @@ impl Resource {
         } else {
             re2
         };
-        (re, elems, is_dynamic)
+        (re, elems, segments, is_dynamic)
+    }
+}
+
+/// Route table entry, describes a single route of a resource.
+///
+/// Handlers registered with `App::handler()` are not part of route table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteInfo {
+    prefix: String,
+    pattern: String,
+    name: String,
+    methods: Option<Vec<Method>>,
+    predicates: Vec<String>,
+    operation: Option<Operation>,
+}
+
+impl RouteInfo {
+    pub(crate) fn new(
+        prefix: &str, pattern: &str, name: &str, methods: Option<Vec<Method>>,
+        predicates: Vec<String>, operation: Option<Operation>,
+    ) -> RouteInfo {
+        RouteInfo {
+            methods,
+            predicates,
+            operation,
+            prefix: prefix.trim_right_matches('/').to_owned(),
+            pattern: pattern.to_owned(),
+            name: name.to_owned(),
+        }
+    }
+
+    /// Application prefix
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Path pattern of the resource
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Full path pattern, application prefix followed by resource pattern
+    pub fn path(&self) -> String {
+        if self.pattern.starts_with('/') {
+            format!("{}{}", self.prefix, self.pattern)
+        } else {
+            format!("{}/{}", self.prefix, self.pattern)
+        }
+    }
+
+    /// Name of the resource
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Methods accepted by route, `None` if route accepts any method
+    pub fn methods(&self) -> Option<&[Method]> {
+        self.methods.as_ref().map(|methods| methods.as_slice())
+    }
+
+    /// Descriptions of route predicates, see `Predicate::describe()`
+    pub fn predicates(&self) -> &[String] {
+        &self.predicates
+    }
+
+    /// OpenAPI description of the route, set with `Route::doc()`
+    pub fn operation(&self) -> Option<&Operation> {
+        self.operation.as_ref()
+    }
+}
+
+/// Formats entry as a route table line, i.e.
+/// `GET,HEAD /app/user/{id} (user) [header(x-api: 1)]`
+impl fmt::Display for RouteInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.methods {
+            Some(ref methods) => {
+                let methods: Vec<_> = methods.iter().map(|m| m.as_str()).collect();
+                write!(f, "{}", methods.join(","))?;
+            }
+            None => write!(f, "*")?,
+        }
+        write!(f, " {}", self.path())?;
+        if !self.name.is_empty() {
+            write!(f, " ({})", self.name)?;
+        }
+        if !self.predicates.is_empty() {
+            write!(f, " [{}]", self.predicates.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Conflict between resource patterns
+///
+/// Router uses first resource that matches request's path, so
+/// a resource that comes after more general one is never used.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RouteConflict {
+    /// Pattern is registered more than once, parameter names are
+    /// not taken into account. Contains pattern and first pattern.
+    Duplicate(String, String),
+    /// Every path that matches pattern is matched by earlier pattern.
+    /// Contains pattern and earlier pattern.
+    Shadowed(String, String),
+}
+
+impl RouteConflict {
+    /// Pattern of resource that is never used
+    pub fn pattern(&self) -> &str {
+        match *self {
+            RouteConflict::Duplicate(ref pattern, _)
+            | RouteConflict::Shadowed(ref pattern, _) => pattern,
+        }
+    }
+
+    /// Pattern of resource that is used instead
+    pub fn matched_by(&self) -> &str {
+        match *self {
+            RouteConflict::Duplicate(_, ref pattern)
+            | RouteConflict::Shadowed(_, ref pattern) => pattern,
+        }
+    }
+}
+
+impl fmt::Display for RouteConflict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RouteConflict::Duplicate(ref pattern, ref first) => write!(
+                f,
+                "Resource \"{}\" duplicates resource \"{}\"",
+                pattern, first
+            ),
+            RouteConflict::Shadowed(ref pattern, ref by) => write!(
+                f,
+                "Resource \"{}\" is shadowed by resource \"{}\"",
+                pattern, by
+            ),
+        }
+    }
+}
+
+/// What to do with route conflicts during application build
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConflictPolicy {
+    /// Do not check routes
+    Ignore,
+    /// Log warning for every conflict
+    Warn,
+    /// Panic on first conflict
+    Panic,
+}
+
+impl Default for ConflictPolicy {
+    fn default() -> ConflictPolicy {
+        ConflictPolicy::Warn
+    }
+}
+
+impl ConflictPolicy {
+    pub(crate) fn check(self, conflicts: &[RouteConflict]) {
+        match self {
+            ConflictPolicy::Ignore => (),
+            ConflictPolicy::Warn => for conflict in conflicts {
+                warn!("{}", conflict);
+            },
+            ConflictPolicy::Panic => if let Some(conflict) = conflicts.first() {
+                panic!("{}", conflict);
+            },
+        }
+    }
+}
+
+/// Find resources that could never be matched.
+///
+/// Check is conservative, only conflicts that could be proved
+/// on path segments level are reported, i.e. `/{name}` shadows
+/// `/index.html` and `/v/{tail}*` shadows `/v/{id}/info`,
+/// but partially overlapping custom patterns are not reported.
+pub(crate) fn find_conflicts<'a, I>(resources: I) -> Vec<RouteConflict>
+where
+    I: IntoIterator<Item = &'a Resource>,
+{
+    let mut conflicts = Vec::new();
+    let mut seen: Vec<(&Resource, Vec<Vec<PathPart>>)> = Vec::new();
+
+    for resource in resources {
+        let parts: Vec<_> = variants(&resource.segments)
+            .iter()
+            .map(|variant| path_parts(variant))
+            .collect();
+        for &(prev, ref prev_parts) in &seen {
+            if *prev_parts == parts {
+                conflicts.push(RouteConflict::Duplicate(
+                    resource.pattern().to_owned(),
+                    prev.pattern().to_owned(),
+                ));
+                break;
+            } else if parts
+                .iter()
+                .all(|parts| prev_parts.iter().any(|prev| covers(prev, parts)))
+            {
+                conflicts.push(RouteConflict::Shadowed(
+                    resource.pattern().to_owned(),
+                    prev.pattern().to_owned(),
+                ));
+                break;
+            }
+        }
+        seen.push((resource, parts));
+    }
+    conflicts
+}
+
+/// Single path segment of a pattern
+#[derive(Debug, PartialEq)]
+enum PathPart {
+    /// Static text
+    Literal(String),
+    /// Whole segment is a parameter with default pattern
+    Param,
+    /// Mix of static text and parameters, flag is set if segment
+    /// contains parameter with custom pattern
+    Complex(String, bool),
+    /// Tail parameter or parameter with `.*` pattern at the end,
+    /// matches rest of the path
+    Tail,
+}
+
+fn path_parts(segments: &[Segment]) -> Vec<PathPart> {
+    fn part(text: String, params: usize, custom: bool) -> PathPart {
+        if params == 0 {
+            PathPart::Literal(text)
+        } else if text == "{}" {
+            PathPart::Param
+        } else {
+            PathPart::Complex(text, custom)
+        }
+    }
+
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut params = 0;
+    let mut custom = false;
+
+    for (idx, segment) in segments.iter().enumerate() {
+        match *segment {
+            Segment::Static(ref s) => {
+                let s = if idx == 0 && s.starts_with('/') {
+                    &s[1..]
+                } else {
+                    &s[..]
+                };
+                let mut pieces = s.split('/');
+                if let Some(piece) = pieces.next() {
+                    text.push_str(piece);
+                }
+                for piece in pieces {
+                    let prev = mem::replace(&mut text, piece.to_owned());
+                    parts.push(part(prev, params, custom));
+                    params = 0;
+                    custom = false;
+                }
+            }
+            Segment::Param(_) => {
+                text.push_str("{}");
+                params += 1;
+            }
+            Segment::Custom(_, ref pattern) => {
+                if pattern == ".*" && text.is_empty() && idx == segments.len() - 1 {
+                    parts.push(PathPart::Tail);
+                    return parts;
+                }
+                text.push_str(&format!("{{:{}}}", pattern));
+                params += 1;
+                custom = true;
+            }
+            Segment::Tail(_) => {
+                if text.is_empty() {
+                    parts.push(PathPart::Tail);
+                    return parts;
+                }
+                text.push_str("{:.*}");
+                params += 1;
+                custom = true;
+            }
+            Segment::Optional(ref param) => {
+                panic!("Optional parameter {:?} has to be expanded", param)
+            }
+        }
+    }
+    parts.push(part(text, params, custom));
+    parts
+}
+
+/// Check if every path matched by `b` is matched by `a`
+fn covers(a: &[PathPart], b: &[PathPart]) -> bool {
+    match (a.split_first(), b.split_first()) {
+        (None, None) => true,
+        (Some((&PathPart::Tail, _)), Some(_)) => true,
+        (Some((x, a)), Some((y, b))) => {
+            let covered = match (x, y) {
+                (&PathPart::Param, &PathPart::Literal(ref s)) => !s.is_empty(),
+                (&PathPart::Param, &PathPart::Param) => true,
+                (&PathPart::Param, &PathPart::Complex(_, custom)) => !custom,
+                (&PathPart::Literal(ref x), &PathPart::Literal(ref y)) => x == y,
+                (&PathPart::Complex(ref x, _), &PathPart::Complex(ref y, _)) => x == y,
+                _ => false,
+            };
+            covered && covers(a, b)
+        }
+        _ => false,
     }
 }
 
+/// Element of a path pattern
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// Static text
+    Static(String),
+    /// Parameter with default pattern, `{name}`
+    Param(String),
+    /// Parameter with custom regex, `{name:regex}`
+    Custom(String, String),
+    /// Rest of the path, `{name}*`
+    Tail(String),
+    /// Optional trailing parameter, `/{name}?`
+    Optional(Box<Segment>),
+}
+
+/// Patterns without optional parameters, from the shortest one
+/// to the pattern with all optional parameters present
+fn variants(segments: &[Segment]) -> Vec<Vec<Segment>> {
+    let mut variants = Vec::new();
+    let mut current = Vec::new();
+
+    for segment in segments {
+        if let Segment::Optional(ref param) = *segment {
+            // drop slash before optional parameter, but keep root path
+            let mut variant = current.clone();
+            if variant != [Segment::Static("/".to_owned())] {
+                let empty = match variant.last_mut() {
+                    Some(&mut Segment::Static(ref mut s)) => {
+                        s.pop();
+                        s.is_empty()
+                    }
+                    _ => false,
+                };
+                if empty {
+                    variant.pop();
+                }
+            }
+            variants.push(variant);
+            current.push((**param).clone());
+        } else {
+            current.push(segment.clone());
+        }
+    }
+    variants.push(current);
+    variants
+}
+
+/// Prefix tree of resource patterns.
+///
+/// Static text is stored in compressed nodes, parameters with default
+/// pattern consume characters up to the next `/` and are tried from
+/// the longest to the shortest value, tail parameter consumes the rest
+/// of the path. Rest of the pattern, starting with a parameter with
+/// custom pattern or with one of several parameters of a path segment,
+/// is matched with a single regex. Pattern with optional parameters
+/// is inserted once for every combination of present parameters.
+///
+/// If path matches several patterns, pattern that was registered first
+/// wins, so result is the same as for sequential matching.
+struct Tree {
+    root: Node,
+}
+
+type Captures<'a> = Vec<(&'a str, &'a str)>;
+
+struct Node {
+    kind: NodeKind,
+    /// static children, each starts with different character
+    statics: Vec<Node>,
+    /// parameter children in registration order
+    params: Vec<Node>,
+    /// first resource that ends at this node
+    leaf: Option<usize>,
+    /// first resource in this subtree
+    first: usize,
+}
+
+enum NodeKind {
+    Static(String),
+    Param(String),
+    Tail(String),
+    /// rest of the pattern and names of its parameters
+    Regex(Regex, Vec<String>),
+}
+
+impl Tree {
+    fn new() -> Tree {
+        Tree {
+            root: Node::new(NodeKind::Static(String::new())),
+        }
+    }
+
+    fn insert(&mut self, segments: &[Segment], idx: usize) {
+        for variant in variants(segments) {
+            self.root.insert(&variant, idx);
+        }
+    }
+
+    /// Find first resource that matches path
+    fn recognize<'a>(&'a self, path: &'a str) -> Option<(usize, Captures<'a>)> {
+        let mut found = None;
+        self.root.search(path, &mut Vec::new(), &mut found);
+        found
+    }
+}
+
+impl Node {
+    fn new(kind: NodeKind) -> Node {
+        Node {
+            kind,
+            statics: Vec::new(),
+            params: Vec::new(),
+            leaf: None,
+            first: usize::max_value(),
+        }
+    }
+
+    fn prefix(&self) -> &str {
+        match self.kind {
+            NodeKind::Static(ref s) => s,
+            _ => "",
+        }
+    }
+
+    fn insert(&mut self, segments: &[Segment], idx: usize) {
+        self.first = cmp::min(self.first, idx);
+
+        let (segment, rest) = match segments.split_first() {
+            Some(item) => item,
+            None => {
+                if self.leaf.is_none() {
+                    self.leaf = Some(idx);
+                }
+                return;
+            }
+        };
+        if needs_regex(segments) {
+            return self.insert_regex(segments, idx);
+        }
+        match *segment {
+            Segment::Static(ref s) => self.insert_static(s, rest, idx),
+            Segment::Param(ref name) => {
+                let pos = self.params.iter().position(|node| match node.kind {
+                    NodeKind::Param(ref n) => n == name,
+                    _ => false,
+                });
+                let pos = pos.unwrap_or_else(|| {
+                    self.params.push(Node::new(NodeKind::Param(name.clone())));
+                    self.params.len() - 1
+                });
+                self.params[pos].insert(rest, idx);
+            }
+            Segment::Tail(ref name) => {
+                let pos = self.params.iter().position(|node| match node.kind {
+                    NodeKind::Tail(ref n) => n == name,
+                    _ => false,
+                });
+                let pos = pos.unwrap_or_else(|| {
+                    self.params.push(Node::new(NodeKind::Tail(name.clone())));
+                    self.params.len() - 1
+                });
+                self.params[pos].insert(rest, idx);
+            }
+            Segment::Custom(..) => unreachable!(),
+            Segment::Optional(_) => {
+                panic!("Optional parameters has to be expanded before insert")
+            }
+        }
+    }
+
+    fn insert_regex(&mut self, segments: &[Segment], idx: usize) {
+        let mut pattern = String::from("^");
+        for segment in segments {
+            match *segment {
+                Segment::Static(ref s) => pattern.push_str(&escape(s)),
+                Segment::Param(ref name) => {
+                    pattern.push_str(&format!(r"(?P<{}>[^/]+)", name))
+                }
+                Segment::Custom(ref name, ref re) => {
+                    pattern.push_str(&format!(r"(?P<{}>{})", name, re))
+                }
+                Segment::Tail(ref name) => {
+                    pattern.push_str(&format!(r"(?P<{}>.*)", name))
+                }
+                Segment::Optional(_) => {
+                    panic!("Optional parameters has to be expanded before insert")
+                }
+            }
+        }
+        pattern.push('$');
+
+        let pos = self.params.iter().position(|node| match node.kind {
+            NodeKind::Regex(ref re, _) => re.as_str() == pattern,
+            _ => false,
+        });
+        let pos = pos.unwrap_or_else(|| {
+            let re = match Regex::new(&pattern) {
+                Ok(re) => re,
+                Err(err) => panic!("Wrong path pattern: \"{}\" {}", pattern, err),
+            };
+            let names = re.capture_names()
+                .filter_map(|name| name.map(|name| name.to_owned()))
+                .collect();
+            self.params.push(Node::new(NodeKind::Regex(re, names)));
+            self.params.len() - 1
+        });
+        self.params[pos].insert(&[], idx);
+    }
+
+    fn insert_static(&mut self, s: &str, rest: &[Segment], idx: usize) {
+        self.first = cmp::min(self.first, idx);
+
+        let ch = s.chars().next();
+        let pos = self.statics
+            .iter()
+            .position(|node| node.prefix().chars().next() == ch);
+        let pos = match pos {
+            Some(pos) => pos,
+            None => {
+                let mut node = Node::new(NodeKind::Static(s.to_owned()));
+                node.insert(rest, idx);
+                self.statics.push(node);
+                return;
+            }
+        };
+
+        let child = &mut self.statics[pos];
+        let common = common_prefix(child.prefix(), s);
+        if common < child.prefix().len() {
+            child.split(common);
+        }
+        if common == s.len() {
+            child.insert(rest, idx);
+        } else {
+            child.insert_static(&s[common..], rest, idx);
+        }
+    }
+
+    /// Move tail of static prefix to a new child node
+    fn split(&mut self, at: usize) {
+        let (head, tail) = {
+            let prefix = self.prefix();
+            (prefix[..at].to_owned(), prefix[at..].to_owned())
+        };
+        let child = Node {
+            kind: NodeKind::Static(tail),
+            statics: mem::replace(&mut self.statics, Vec::new()),
+            params: mem::replace(&mut self.params, Vec::new()),
+            leaf: self.leaf.take(),
+            first: self.first,
+        };
+        self.kind = NodeKind::Static(head);
+        self.statics.push(child);
+    }
+
+    fn search<'a>(
+        &'a self, path: &'a str, captures: &mut Captures<'a>,
+        found: &mut Option<(usize, Captures<'a>)>,
+    ) {
+        // nothing in this subtree could win over already found resource
+        if let Some((idx, _)) = *found {
+            if self.first >= idx {
+                return;
+            }
+        }
+
+        if path.is_empty() {
+            if let Some(leaf) = self.leaf {
+                let better = match *found {
+                    Some((idx, _)) => leaf < idx,
+                    None => true,
+                };
+                if better {
+                    *found = Some((leaf, captures.clone()));
+                }
+            }
+        }
+
+        if let Some(ch) = path.chars().next() {
+            for node in &self.statics {
+                let prefix = node.prefix();
+                if prefix.chars().next() == Some(ch) {
+                    if path.starts_with(prefix) {
+                        node.search(&path[prefix.len()..], captures, found);
+                    }
+                    break;
+                }
+            }
+        }
+
+        for node in &self.params {
+            match node.kind {
+                NodeKind::Param(ref name) => {
+                    let mut end = path.find('/').unwrap_or_else(|| path.len());
+                    while end > 0 {
+                        node.capture(name, path, end, captures, found);
+                        end -= 1;
+                        while !path.is_char_boundary(end) {
+                            end -= 1;
+                        }
+                    }
+                }
+                NodeKind::Tail(ref name) => {
+                    node.capture(name, path, path.len(), captures, found);
+                }
+                NodeKind::Regex(ref re, ref names) => {
+                    if let Some(caps) = re.captures(path) {
+                        let len = captures.len();
+                        for name in names {
+                            if let Some(m) = caps.name(name) {
+                                captures.push((name.as_str(), m.as_str()));
+                            }
+                        }
+                        node.search("", captures, found);
+                        captures.truncate(len);
+                    }
+                }
+                NodeKind::Static(_) => (),
+            }
+        }
+    }
+
+    fn capture<'a>(
+        &'a self, name: &'a str, path: &'a str, end: usize,
+        captures: &mut Captures<'a>, found: &mut Option<(usize, Captures<'a>)>,
+    ) {
+        captures.push((name, &path[..end]));
+        self.search(&path[end..], captures, found);
+        captures.pop();
+    }
+}
+
+/// Check if pattern starts with a parameter that is matched with regex,
+/// parameter with custom pattern or one of several parameters of a path
+/// segment. Default parameters are tried for every possible length, so
+/// several of them in a segment would take polynomial time.
+fn needs_regex(segments: &[Segment]) -> bool {
+    match segments.split_first() {
+        Some((&Segment::Custom(..), _)) => true,
+        Some((&Segment::Param(_), rest)) | Some((&Segment::Tail(_), rest)) => {
+            for segment in rest {
+                match *segment {
+                    Segment::Static(ref s) => if s.contains('/') {
+                        return false;
+                    },
+                    _ => return true,
+                }
+            }
+            false
+        }
+        _ => false,
+    }
+}
+
+/// Length of common prefix, at char boundary
+fn common_prefix(a: &str, b: &str) -> usize {
+    let mut len = a.bytes()
+        .zip(b.bytes())
+        .take_while(|&(a, b)| a == b)
+        .count();
+    while !a.is_char_boundary(len) {
+        len -= 1;
+    }
+    len
+}
+
 impl PartialEq for Resource {
     fn eq(&self, other: &Resource) -> bool {
         self.pattern == other.pattern
@@ -382,6 +1239,7 @@ impl Hash for Resource {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::{Duration, Instant};
     use test::TestRequest;
 
     #[test]
@@ -474,6 +1332,277 @@ mod tests {
         assert_eq!(rec.recognize(&mut req), Some(1));
     }
 
+    #[test]
+    fn test_recognizer_priority() {
+        let routes = vec![
+            (
+                Resource::new("", "/{source}.json"),
+                Some(ResourceHandler::default()),
+            ),
+            (
+                Resource::new("", "/index.json"),
+                Some(ResourceHandler::default()),
+            ),
+            (
+                Resource::new("", "/user/{id:\\d+}"),
+                Some(ResourceHandler::default()),
+            ),
+            (
+                Resource::new("", "/user/{name}"),
+                Some(ResourceHandler::default()),
+            ),
+            (
+                Resource::new("", "/user/{name}/{tail:.*}"),
+                Some(ResourceHandler::default()),
+            ),
+            (
+                Resource::new("", "/user/{name}/profile"),
+                Some(ResourceHandler::default()),
+            ),
+        ];
+        let (rec, _) = Router::new::<()>("", ServerSettings::default(), routes);
+
+        // first registered pattern wins
+        let mut req = TestRequest::with_uri("/index.json").finish();
+        assert_eq!(rec.recognize(&mut req), Some(0));
+        assert_eq!(&req.match_info()["source"], "index");
+
+        let mut req = TestRequest::with_uri("/user/123").finish();
+        assert_eq!(rec.recognize(&mut req), Some(2));
+        assert_eq!(&req.match_info()["id"], "123");
+
+        let mut req = TestRequest::with_uri("/user/bob").finish();
+        assert_eq!(rec.recognize(&mut req), Some(3));
+        assert_eq!(&req.match_info()["name"], "bob");
+
+        let mut req = TestRequest::with_uri("/user/bob/profile").finish();
+        assert_eq!(rec.recognize(&mut req), Some(4));
+        assert_eq!(&req.match_info()["tail"], "profile");
+
+        let mut req = TestRequest::with_uri("/user/b%C3%B6b").finish();
+        assert_eq!(rec.recognize(&mut req), Some(3));
+        assert_eq!(&req.match_info()["name"], "böb");
+
+        assert!(rec.has_route("/user/bob/"));
+        assert!(!rec.has_route("/user/"));
+        assert!(!rec.has_route("/index.html"));
+    }
+
+    #[test]
+    fn test_tree() {
+        let mut tree = Tree::new();
+        for (idx, pattern) in ["/name", "/names", "/na{val}", "/n", "/name"]
+            .iter()
+            .enumerate()
+        {
+            tree.insert(&Resource::new("", pattern).segments, idx);
+        }
+        assert_eq!(tree.root.statics.len(), 1);
+        assert_eq!(tree.root.statics[0].prefix(), "/n");
+        assert_eq!(tree.root.statics[0].leaf, Some(3));
+
+        assert_eq!(tree.recognize("/name"), Some((0, vec![])));
+        assert_eq!(tree.recognize("/names"), Some((1, vec![])));
+        assert_eq!(tree.recognize("/nameless"), Some((2, vec![("val", "meless")])));
+        assert_eq!(tree.recognize("/n"), Some((3, vec![])));
+        assert_eq!(tree.recognize("/na"), None);
+        assert_eq!(tree.recognize("/na/me"), None);
+    }
+
+    #[test]
+    fn test_tree_regex() {
+        let mut tree = Tree::new();
+        for (idx, pattern) in [
+            "/{name:[^/]+}.html",
+            "/files/{path:.*}/raw",
+            "/x/{v:a|ab}",
+            "/file/{name}.{ext}",
+        ].iter()
+            .enumerate()
+        {
+            tree.insert(&Resource::new("", pattern).segments, idx);
+        }
+
+        assert_eq!(
+            tree.recognize("/index.html"),
+            Some((0, vec![("name", "index")]))
+        );
+        assert_eq!(
+            tree.recognize("/files/a/b/raw"),
+            Some((1, vec![("path", "a/b")]))
+        );
+        assert_eq!(tree.recognize("/x/ab"), Some((2, vec![("v", "ab")])));
+        assert_eq!(tree.recognize("/x/abc"), None);
+        assert_eq!(
+            tree.recognize("/file/main.tar.gz"),
+            Some((3, vec![("name", "main.tar"), ("ext", "gz")]))
+        );
+    }
+
+    #[test]
+    fn test_tree_cost() {
+        let mut tree = Tree::new();
+        tree.insert(&Resource::new("", "/{name}.{ext}").segments, 0);
+        tree.insert(&Resource::new("", "/{name}.html").segments, 1);
+
+        let path = format!("/{}/", ".".repeat(32 * 1024));
+        let start = Instant::now();
+        assert_eq!(tree.recognize(&path), None);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let resources = vec![
+            Resource::new("", "/index.html"),
+            Resource::new("", "/{name}"),
+            Resource::new("", "/index.json"),
+            Resource::new("", "/{id}"),
+            Resource::new("", "/user/{id:\\d+}"),
+            Resource::new("", "/user/{name}"),
+            Resource::new("", "/user/{id:\\d+}"),
+            Resource::new("", "/file/{file}.{ext}"),
+            Resource::new("", "/file/{name}.{ext}"),
+            Resource::new("", "/v/{tail:.*}"),
+            Resource::new("", "/v/{id}/info"),
+            Resource::new("", "/v"),
+            Resource::new("", "/{name}/"),
+        ];
+        assert_eq!(
+            find_conflicts(&resources),
+            vec![
+                RouteConflict::Shadowed("/index.json".to_owned(), "/{name}".to_owned()),
+                RouteConflict::Duplicate("/{id}".to_owned(), "/{name}".to_owned()),
+                RouteConflict::Duplicate(
+                    "/user/{id:\\d+}".to_owned(),
+                    "/user/{id:\\d+}".to_owned()
+                ),
+                RouteConflict::Duplicate(
+                    "/file/{name}.{ext}".to_owned(),
+                    "/file/{file}.{ext}".to_owned()
+                ),
+                RouteConflict::Shadowed(
+                    "/v/{id}/info".to_owned(),
+                    "/v/{tail:.*}".to_owned()
+                ),
+                RouteConflict::Shadowed("/v".to_owned(), "/{name}".to_owned()),
+            ]
+        );
+
+        let parts = |path| path_parts(&Resource::new("", path).segments);
+        assert!(covers(&parts("/v/{tail:.*}"), &parts("/v/")));
+        assert!(!covers(&parts("/v/{tail:.*}"), &parts("/v")));
+        assert!(!covers(&parts("/{name}"), &parts("/")));
+        assert!(!covers(&parts("/{name}"), &parts("/{name:.*}")));
+
+        let conflict = RouteConflict::Shadowed("/a".to_owned(), "/{b}".to_owned());
+        assert_eq!(conflict.pattern(), "/a");
+        assert_eq!(conflict.matched_by(), "/{b}");
+        assert_eq!(
+            conflict.to_string(),
+            "Resource \"/a\" is shadowed by resource \"/{b}\""
+        );
+    }
+
+    #[test]
+    fn test_tail_and_optional() {
+        let mut tree = Tree::new();
+        for (idx, pattern) in [
+            "/static/{tail}*",
+            "/archive/{year}?/{month:\\d+}?",
+            "/{page}?",
+        ].iter()
+            .enumerate()
+        {
+            tree.insert(&Resource::new("", pattern).segments, idx);
+        }
+
+        assert_eq!(tree.recognize("/static/"), Some((0, vec![("tail", "")])));
+        assert_eq!(
+            tree.recognize("/static/css/main.css"),
+            Some((0, vec![("tail", "css/main.css")]))
+        );
+        assert_eq!(tree.recognize("/static"), Some((2, vec![("page", "static")])));
+        assert_eq!(tree.recognize("/archive"), Some((1, vec![])));
+        assert_eq!(
+            tree.recognize("/archive/2018"),
+            Some((1, vec![("year", "2018")]))
+        );
+        assert_eq!(
+            tree.recognize("/archive/2018/04"),
+            Some((1, vec![("year", "2018"), ("month", "04")]))
+        );
+        assert_eq!(tree.recognize("/archive/2018/apr"), None);
+        assert_eq!(tree.recognize("/archive/"), None);
+        assert_eq!(tree.recognize("/"), Some((2, vec![])));
+        assert_eq!(tree.recognize("/index"), Some((2, vec![("page", "index")])));
+
+        let resources = vec![
+            Resource::new("", "/user/{id}/{action}?"),
+            Resource::new("", "/user/{name}"),
+            Resource::new("", "/files/{path}*"),
+            Resource::new("", "/files/{name}/{tail:.*}"),
+            Resource::new("", "/files/{dir}/{file}?"),
+        ];
+        assert_eq!(
+            find_conflicts(&resources),
+            vec![
+                RouteConflict::Shadowed(
+                    "/user/{name}".to_owned(),
+                    "/user/{id}/{action}?".to_owned()
+                ),
+                RouteConflict::Shadowed(
+                    "/files/{name}/{tail:.*}".to_owned(),
+                    "/files/{path}*".to_owned()
+                ),
+                RouteConflict::Shadowed(
+                    "/files/{dir}/{file}?".to_owned(),
+                    "/files/{path}*".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "tail parameter has to be at the end")]
+    fn test_tail_not_at_end() {
+        Resource::new("", "/files/{tail}*/index.html");
+    }
+
+    #[test]
+    #[should_panic(expected = "only optional parameters could follow")]
+    fn test_optional_not_at_end() {
+        Resource::new("", "/archive/{year}?/{month}");
+    }
+
+    #[test]
+    #[should_panic(expected = "optional parameter has to be a whole segment")]
+    fn test_optional_segment() {
+        Resource::new("", "/archive/v{year}?");
+    }
+
+    #[test]
+    fn test_route_info() {
+        let info = RouteInfo::new(
+            "/app/",
+            "user/{id}",
+            "user",
+            Some(vec![Method::GET, Method::HEAD]),
+            vec!["header(x-api: 1)".to_owned()],
+            None,
+        );
+        assert_eq!(info.prefix(), "/app");
+        assert_eq!(info.path(), "/app/user/{id}");
+        assert_eq!(
+            info.to_string(),
+            "GET,HEAD /app/user/{id} (user) [header(x-api: 1)]"
+        );
+
+        let info = RouteInfo::new("/", "/index.html", "", None, Vec::new(), None);
+        assert_eq!(info.methods(), None);
+        assert_eq!(info.to_string(), "* /index.html");
+    }
+
     #[test]
     fn test_recognizer_with_prefix() {
         let routes = vec![
@@ -574,6 +1703,78 @@ mod tests {
         assert_eq!(req.match_info().get("id").unwrap(), "adahg32");
     }
 
+    #[test]
+    fn test_parse_tail() {
+        let mut req = HttpRequest::default();
+
+        let re = Resource::new("test", "/static/{tail}*");
+        assert!(re.is_match("/static/"));
+        assert!(re.is_match("/static/css/main.css"));
+        assert!(!re.is_match("/static"));
+
+        req.match_info_mut().clear();
+        assert!(re.match_with_params("/static/css/main.css", req.match_info_mut()));
+        assert_eq!(req.match_info().get("tail").unwrap(), "css/main.css");
+    }
+
+    #[test]
+    fn test_parse_optional() {
+        let mut req = HttpRequest::default();
+
+        let re = Resource::new("test", "/archive/{year}?/{month}?");
+        assert!(re.is_match("/archive"));
+        assert!(re.is_match("/archive/2018"));
+        assert!(re.is_match("/archive/2018/04"));
+        assert!(!re.is_match("/archive/"));
+        assert!(!re.is_match("/archive/2018/04/01"));
+
+        req.match_info_mut().clear();
+        assert!(re.match_with_params("/archive/2018", req.match_info_mut()));
+        assert_eq!(req.match_info().len(), 1);
+        assert_eq!(req.match_info().get("year").unwrap(), "2018");
+
+        let re = Resource::new("test", "/{page}?");
+        assert!(re.is_match("/"));
+        assert!(re.is_match("/index"));
+        assert!(!re.is_match("/index/"));
+    }
+
+    #[test]
+    fn test_resource_path_tail_and_optional() {
+        let routes = vec![
+            (
+                Resource::new("static", "/static/{tail}*"),
+                Some(ResourceHandler::<()>::default()),
+            ),
+            (
+                Resource::new("archive", "/archive/{year}?/{month}?"),
+                Some(ResourceHandler::<()>::default()),
+            ),
+            (
+                Resource::new("page", "/{page}?"),
+                Some(ResourceHandler::<()>::default()),
+            ),
+        ];
+        let (router, _) = Router::new::<()>("", ServerSettings::default(), routes);
+
+        assert_eq!(
+            router.resource_path("static", &["css/main.css"]).unwrap(),
+            "/static/css/main.css"
+        );
+        let empty: &[&str] = &[];
+        assert_eq!(router.resource_path("archive", empty).unwrap(), "/archive");
+        assert_eq!(
+            router.resource_path("archive", &["2018"]).unwrap(),
+            "/archive/2018"
+        );
+        assert_eq!(
+            router.resource_path("archive", &["2018", "04"]).unwrap(),
+            "/archive/2018/04"
+        );
+        assert_eq!(router.resource_path("page", empty).unwrap(), "/");
+        assert_eq!(router.resource_path("page", &["index"]).unwrap(), "/index");
+    }
+
     #[test]
     fn test_request_resource() {
         let routes = vec![
@@ -455,6 +455,22 @@ impl ContentEncoder {
                 }
                 TransferEncoding::eof(buf)
             }
+            Body::File(ref file) => {
+                if encoding.is_compression() || resp.chunked() == Some(true) {
+                    // length of the encoded file is not known
+                    resp.headers_mut().remove(CONTENT_LENGTH);
+                    ContentEncoder::streaming_encoding(buf, version, resp)
+                } else {
+                    let len = file.remaining();
+                    let mut b = BytesMut::new();
+                    let _ = write!(b, "{}", len);
+                    resp.headers_mut().insert(
+                        CONTENT_LENGTH,
+                        HeaderValue::try_from(b.freeze()).unwrap(),
+                    );
+                    TransferEncoding::length(len, buf)
+                }
+            }
             Body::Streaming(_) | Body::Actor(_) => {
                 if resp.upgrade() {
                     if version == Version::HTTP_2 {
@@ -561,6 +577,16 @@ impl ContentEncoder {
 }
 
 impl ContentEncoder {
+    /// Check if encoder writes payload as is, without compression
+    /// or chunked transfer encoding
+    #[inline]
+    pub fn is_passthrough(&self) -> bool {
+        match *self {
+            ContentEncoder::Identity(ref encoder) => !encoder.is_chunked(),
+            _ => false,
+        }
+    }
+
     #[inline]
     pub fn is_eof(&self) -> bool {
         match *self {
@@ -700,6 +726,14 @@ impl TransferEncoding {
         }
     }
 
+    #[inline]
+    pub fn is_chunked(&self) -> bool {
+        match self.kind {
+            TransferEncodingKind::Chunked(_) => true,
+            _ => false,
+        }
+    }
+
     #[inline]
     pub fn is_eof(&self) -> bool {
         match self.kind {
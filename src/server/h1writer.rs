@@ -4,6 +4,7 @@ use bytes::BufMut;
 use futures::{Async, Poll};
 use http::header::{HeaderValue, CONNECTION, CONTENT_LENGTH, DATE};
 use http::{Method, Version};
+use std::fs::File;
 use std::rc::Rc;
 use std::{io, mem};
 use tokio_io::AsyncWrite;
@@ -12,7 +13,7 @@ use super::encoding::ContentEncoder;
 use super::helpers;
 use super::settings::WorkerSettings;
 use super::shared::SharedBytes;
-use super::{Writer, WriterState, MAX_WRITE_BUFFER_SIZE};
+use super::{IoStream, SendfileReady, Writer, WriterState, MAX_WRITE_BUFFER_SIZE};
 use body::{Binary, Body};
 use header::ContentEncoding;
 use httprequest::HttpInnerMessage;
@@ -26,6 +27,8 @@ bitflags! {
         const UPGRADE = 0b0000_0010;
         const KEEPALIVE = 0b0000_0100;
         const DISCONNECTED = 0b0000_1000;
+        const SENDFILE = 0b0001_0000;
+        const SENDFILE_BLOCKED = 0b0010_0000;
     }
 }
 
@@ -37,6 +40,7 @@ pub(crate) struct H1Writer<T: AsyncWrite, H: 'static> {
     headers_size: u32,
     buffer: SharedBytes,
     buffer_capacity: usize,
+    sendfile_ready: Option<SendfileReady>,
     settings: Rc<WorkerSettings<H>>,
 }
 
@@ -51,6 +55,7 @@ impl<T: AsyncWrite, H: 'static> H1Writer<T, H> {
             headers_size: 0,
             buffer: buf,
             buffer_capacity: 0,
+            sendfile_ready: None,
             stream,
             settings,
         }
@@ -94,7 +99,7 @@ impl<T: AsyncWrite, H: 'static> H1Writer<T, H> {
     }
 }
 
-impl<T: AsyncWrite, H: 'static> Writer for H1Writer<T, H> {
+impl<T: IoStream, H: 'static> Writer for H1Writer<T, H> {
     #[inline]
     fn written(&self) -> u64 {
         self.written
@@ -216,6 +221,14 @@ impl<T: AsyncWrite, H: 'static> Writer for H1Writer<T, H> {
             // capacity, makes sense only for streaming or actor
             self.buffer_capacity = msg.write_buffer_capacity();
 
+            // file content bypasses encoder, `Content-Length` is already set
+            if let Body::File(_) = body {
+                if self.encoder.is_passthrough() && self.stream.can_sendfile() {
+                    self.flags.insert(Flags::SENDFILE);
+                    self.encoder = ContentEncoder::empty(self.buffer.clone());
+                }
+            }
+
             msg.replace_body(body);
         }
         Ok(WriterState::Done)
@@ -279,10 +292,65 @@ impl<T: AsyncWrite, H: 'static> Writer for H1Writer<T, H> {
                 return Ok(Async::NotReady);
             }
         }
+        if self.flags.contains(Flags::SENDFILE_BLOCKED) {
+            if let Some(ref ready) = self.sendfile_ready {
+                if !ready.poll_write_ready().is_ready() {
+                    return Ok(Async::NotReady);
+                }
+            }
+            self.flags.remove(Flags::SENDFILE_BLOCKED);
+        }
         if shutdown {
             self.stream.shutdown()
         } else {
             Ok(Async::Ready(()))
         }
     }
+
+    #[inline]
+    fn can_sendfile(&self) -> bool {
+        self.flags.contains(Flags::SENDFILE)
+    }
+
+    fn sendfile(
+        &mut self, file: &File, offset: u64, count: u64
+    ) -> io::Result<(u64, WriterState)> {
+        if self.flags.contains(Flags::DISCONNECTED) {
+            return Ok((count, WriterState::Done));
+        }
+
+        // headers and previously buffered data go first
+        if !self.buffer.is_empty() {
+            let buf: &[u8] = unsafe { mem::transmute(self.buffer.as_ref()) };
+            let written = self.write_data(buf)?;
+            let _ = self.buffer.split_to(written);
+
+            // socket is not ready, buffered data keeps readiness tracked,
+            // so no file data is copied until buffer is flushed
+            if !self.buffer.is_empty() {
+                return Ok((0, WriterState::Pause));
+            }
+        }
+
+        match self.stream.sendfile(file, offset, count) {
+            Ok(0) => Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => {
+                self.written += n;
+                Ok((n, WriterState::Done))
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::WouldBlock => {
+                // stream does not track readiness for data sent with
+                // `sendfile()`, wait for writable socket separately
+                if self.sendfile_ready.is_none() {
+                    self.sendfile_ready = Some(self.stream.sendfile_ready()?);
+                }
+                if let Some(ref ready) = self.sendfile_ready {
+                    ready.need_write();
+                }
+                self.flags.insert(Flags::SENDFILE_BLOCKED);
+                Ok((0, WriterState::Pause))
+            }
+            Err(err) => Err(err),
+        }
+    }
 }
@@ -1,4 +1,5 @@
 //! Http server
+use std::fs::File;
 use std::net::Shutdown;
 use std::{io, time};
 
@@ -14,6 +15,7 @@ mod h1writer;
 mod h2;
 mod h2writer;
 pub(crate) mod helpers;
+mod sendfile;
 mod settings;
 pub(crate) mod shared;
 mod srv;
@@ -23,6 +25,9 @@ mod worker;
 pub use self::settings::ServerSettings;
 pub use self::srv::HttpServer;
 
+#[doc(hidden)]
+pub use self::sendfile::SendfileReady;
+
 use body::Binary;
 use error::Error;
 use header::ContentEncoding;
@@ -32,6 +37,10 @@ use httpresponse::HttpResponse;
 /// max buffer size 64k
 pub(crate) const MAX_WRITE_BUFFER_SIZE: usize = 65_536;
 
+/// max number of bytes sent with single `sendfile()` call, 1Mb
+#[cfg(target_os = "linux")]
+const MAX_SENDFILE_SIZE: u64 = 1_048_576;
+
 /// Create new http server with application factory.
 ///
 /// This is shortcut for `server::HttpServer::new()` method.
@@ -179,6 +188,24 @@ pub trait Writer {
     fn write_eof(&mut self) -> io::Result<WriterState>;
 
     fn poll_completed(&mut self, shutdown: bool) -> Poll<(), io::Error>;
+
+    /// Check if `Body::File` body of the started response can be sent
+    /// with `sendfile()`
+    fn can_sendfile(&self) -> bool {
+        false
+    }
+
+    /// Send up to `count` bytes of the file starting at `offset`.
+    ///
+    /// Returns number of bytes that got consumed from the file.
+    fn sendfile(
+        &mut self, _file: &File, _offset: u64, _count: u64
+    ) -> io::Result<(u64, WriterState)> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "sendfile is not supported",
+        ))
+    }
 }
 
 #[doc(hidden)]
@@ -189,6 +216,28 @@ pub trait IoStream: AsyncRead + AsyncWrite + 'static {
     fn set_nodelay(&mut self, nodelay: bool) -> io::Result<()>;
 
     fn set_linger(&mut self, dur: Option<time::Duration>) -> io::Result<()>;
+
+    /// Check if stream supports zero-copy file transfer
+    fn can_sendfile(&self) -> bool {
+        false
+    }
+
+    /// Copy up to `count` bytes of the file starting at `offset`
+    /// directly to the stream.
+    fn sendfile(&mut self, _file: &File, _offset: u64, _count: u64) -> io::Result<u64> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "sendfile is not supported",
+        ))
+    }
+
+    /// Write readiness of the stream for data sent with `sendfile()`
+    fn sendfile_ready(&self) -> io::Result<SendfileReady> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "sendfile is not supported",
+        ))
+    }
 }
 
 impl IoStream for TcpStream {
@@ -206,6 +255,38 @@ impl IoStream for TcpStream {
     fn set_linger(&mut self, dur: Option<time::Duration>) -> io::Result<()> {
         TcpStream::set_linger(self, dur)
     }
+
+    #[cfg(target_os = "linux")]
+    #[inline]
+    fn can_sendfile(&self) -> bool {
+        true
+    }
+
+    #[cfg(target_os = "linux")]
+    fn sendfile(&mut self, file: &File, offset: u64, count: u64) -> io::Result<u64> {
+        use libc;
+        use std::cmp;
+        use std::os::unix::io::AsRawFd;
+
+        // do not block worker's event loop on one huge transfer
+        let count = cmp::min(count, MAX_SENDFILE_SIZE) as libc::size_t;
+        let mut offset = offset as libc::off_t;
+        let res = unsafe {
+            libc::sendfile(self.as_raw_fd(), file.as_raw_fd(), &mut offset, count)
+        };
+        if res < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(res as u64)
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn sendfile_ready(&self) -> io::Result<SendfileReady> {
+        use std::os::unix::io::AsRawFd;
+
+        SendfileReady::new(self.as_raw_fd())
+    }
 }
 
 #[cfg(feature = "alpn")]
@@ -0,0 +1,90 @@
+//! Write readiness of a socket that file content is sent to with `sendfile()`
+
+#[cfg(target_os = "linux")]
+pub use self::linux::SendfileReady;
+#[cfg(not(target_os = "linux"))]
+pub use self::other::SendfileReady;
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::io;
+    use std::os::unix::io::RawFd;
+
+    use actix::Arbiter;
+    use futures::Async;
+    use libc;
+    use mio::unix::EventedFd;
+    use mio::{self, Evented, PollOpt, Ready, Token};
+    use tokio_core::reactor::PollEvented;
+
+    /// Duplicate of connection's socket registered in the event loop.
+    ///
+    /// `sendfile()` writes to the socket bypassing stream's own readiness
+    /// tracking, so after `WouldBlock` the worker waits for the socket
+    /// to become writable with this registration.
+    pub struct SendfileReady(PollEvented<SocketFd>);
+
+    impl SendfileReady {
+        pub fn new(fd: RawFd) -> io::Result<SendfileReady> {
+            let fd = unsafe { libc::dup(fd) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            PollEvented::new(SocketFd(fd), Arbiter::handle()).map(SendfileReady)
+        }
+
+        /// Check if socket is writable, current task is notified
+        /// when it becomes writable otherwise
+        pub fn poll_write_ready(&self) -> Async<()> {
+            self.0.poll_write()
+        }
+
+        /// Socket is not writable, notify current task when it becomes
+        /// writable again
+        pub fn need_write(&self) {
+            self.0.need_write()
+        }
+    }
+
+    struct SocketFd(RawFd);
+
+    impl Evented for SocketFd {
+        fn register(
+            &self, poll: &mio::Poll, token: Token, interest: Ready, opts: PollOpt
+        ) -> io::Result<()> {
+            EventedFd(&self.0).register(poll, token, interest, opts)
+        }
+
+        fn reregister(
+            &self, poll: &mio::Poll, token: Token, interest: Ready, opts: PollOpt
+        ) -> io::Result<()> {
+            EventedFd(&self.0).reregister(poll, token, interest, opts)
+        }
+
+        fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+            EventedFd(&self.0).deregister(poll)
+        }
+    }
+
+    impl Drop for SocketFd {
+        fn drop(&mut self) {
+            unsafe { libc::close(self.0) };
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod other {
+    use futures::Async;
+
+    /// `sendfile()` is not supported, readiness is never requested
+    pub struct SendfileReady(());
+
+    impl SendfileReady {
+        pub fn poll_write_ready(&self) -> Async<()> {
+            Async::Ready(())
+        }
+
+        pub fn need_write(&self) {}
+    }
+}
@@ -0,0 +1,444 @@
+//! Request data validation
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use failure::Fail;
+use futures::{Async, Future, Poll};
+use http::StatusCode;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use serde_json;
+
+use error::{Error, ResponseError};
+use handler::FromRequest;
+use httprequest::HttpRequest;
+use httpresponse::HttpResponse;
+
+/// Validation of extracted request data
+///
+/// ```rust
+/// # extern crate actix_web;
+/// #[macro_use] extern crate serde_derive;
+/// use actix_web::error::ValidationErrors;
+/// use actix_web::{http, App, Json, Validate, Validated};
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     name: String,
+///     email: String,
+///     age: u32,
+/// }
+///
+/// impl Validate for User {
+///     fn validate(&self) -> Result<(), ValidationErrors> {
+///         let mut errors = ValidationErrors::new();
+///         errors
+///             .length("name", &self.name, 1, 64)
+///             .email("email", &self.email)
+///             .range("age", self.age, 18, 150);
+///         errors.into_result()
+///     }
+/// }
+///
+/// /// handler get called only if user is valid,
+/// /// otherwise `422 Unprocessable Entity` response is returned
+/// fn index(user: Validated<Json<User>>) -> String {
+///     format!("Welcome {}!", user.name)
+/// }
+///
+/// fn main() {
+///     let app = App::new().resource("/index.html", |r| {
+///         r.method(http::Method::POST).with(index)
+///     });
+/// }
+/// ```
+pub trait Validate {
+    /// Validate value, returns list of field errors if value is not valid
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+/// Validation error of a single field
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldError {
+    field: String,
+    code: &'static str,
+    message: String,
+}
+
+impl FieldError {
+    /// Create field error
+    pub fn new<F: Into<String>, M: Into<String>>(
+        field: F, code: &'static str, message: M
+    ) -> FieldError {
+        FieldError {
+            code,
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Field name
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Machine readable error code, i.e. `length`, `range` or `email`
+    pub fn code(&self) -> &str {
+        self.code
+    }
+
+    /// Human readable error message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Serialize for FieldError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("FieldError", 3)?;
+        s.serialize_field("field", &self.field)?;
+        s.serialize_field("code", self.code)?;
+        s.serialize_field("message", &self.message)?;
+        s.end()
+    }
+}
+
+/// A list of field validation errors
+///
+/// Response for `ValidationErrors` is `422 Unprocessable Entity`
+/// with json body:
+///
+/// ```json
+/// {"errors": [{"field": "name", "code": "length", "message": "..."}]}
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    /// Create empty errors list
+    pub fn new() -> ValidationErrors {
+        ValidationErrors::default()
+    }
+
+    /// Field errors
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// Check if there are no errors
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// `Ok(())` if there are no errors, otherwise `Err(self)`
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Add field error
+    pub fn add(&mut self, error: FieldError) -> &mut Self {
+        self.errors.push(error);
+        self
+    }
+
+    /// Add field error if condition is false
+    pub fn check<M: Into<String>>(
+        &mut self, field: &str, valid: bool, message: M
+    ) -> &mut Self {
+        if !valid {
+            self.add(FieldError::new(field, "invalid", message));
+        }
+        self
+    }
+
+    /// Check that field is present
+    pub fn required<T>(&mut self, field: &str, value: &Option<T>) -> &mut Self {
+        if value.is_none() {
+            self.add(FieldError::new(field, "required", "value is required"));
+        }
+        self
+    }
+
+    /// Check number of characters in a string
+    pub fn length(
+        &mut self, field: &str, value: &str, min: usize, max: usize
+    ) -> &mut Self {
+        let len = value.chars().count();
+        if len < min || len > max {
+            self.add(FieldError::new(
+                field,
+                "length",
+                format!("length must be between {} and {}", min, max),
+            ));
+        }
+        self
+    }
+
+    /// Check that value is in `min..=max` range
+    pub fn range<T>(&mut self, field: &str, value: T, min: T, max: T) -> &mut Self
+    where
+        T: PartialOrd + fmt::Display,
+    {
+        if value < min || value > max {
+            self.add(FieldError::new(
+                field,
+                "range",
+                format!("value must be between {} and {}", min, max),
+            ));
+        }
+        self
+    }
+
+    /// Check that string looks like an email address
+    pub fn email(&mut self, field: &str, value: &str) -> &mut Self {
+        if !is_email(value) {
+            self.add(FieldError::new(field, "email", "invalid email address"));
+        }
+        self
+    }
+
+    /// Add errors of nested value, field names are prefixed with `field.`
+    pub fn nested<T: Validate>(&mut self, field: &str, value: &T) -> &mut Self {
+        if let Err(errors) = value.validate() {
+            for err in errors.errors {
+                self.errors.push(FieldError {
+                    field: format!("{}.{}", field, err.field),
+                    ..err
+                });
+            }
+        }
+        self
+    }
+}
+
+fn is_email(value: &str) -> bool {
+    let mut parts = value.rsplitn(2, '@');
+    let (domain, local) = match (parts.next(), parts.next()) {
+        (Some(domain), Some(local)) => (domain, local),
+        _ => return false,
+    };
+    !local.is_empty() && local.len() <= 64 && domain.len() <= 255
+        && !value.chars().any(|c| c.is_whitespace() || c.is_control())
+        && domain.contains('.')
+        && domain.split('.').all(|label| {
+            !label.is_empty() && !label.starts_with('-') && !label.ends_with('-')
+                && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        })
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Validation failed")?;
+        for (idx, err) in self.errors.iter().enumerate() {
+            let sep = if idx == 0 { ": " } else { ", " };
+            write!(f, "{}{}: {}", sep, err.field, err.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl Fail for ValidationErrors {}
+
+impl Serialize for ValidationErrors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut s = serializer.serialize_struct("ValidationErrors", 1)?;
+        s.serialize_field("errors", &self.errors)?;
+        s.end()
+    }
+}
+
+/// Return `UnprocessableEntity` with json list of field errors
+impl ResponseError for ValidationErrors {
+    fn error_response(&self) -> HttpResponse {
+        match serde_json::to_string(self) {
+            Ok(body) => HttpResponse::build(StatusCode::UNPROCESSABLE_ENTITY)
+                .content_type("application/json")
+                .body(body),
+            Err(_) => HttpResponse::new(StatusCode::UNPROCESSABLE_ENTITY),
+        }
+    }
+}
+
+/// Extractor wrapper that validates extracted value.
+///
+/// Inner extractor has to dereference to a type that implements
+/// `Validate` trait, i.e. `Json<T>`, `Form<T>`, `Query<T>` or `Path<T>`.
+/// If validation fails, request is rejected with `ValidationErrors` error.
+/// Error could be customized with `App::extractor_error_handler()`.
+///
+/// Extractor configuration is the inner extractor's configuration.
+pub struct Validated<T>(pub T);
+
+impl<T> Validated<T> {
+    /// Deconstruct to an inner extractor
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Validated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Validated<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequest<S> for Validated<T>
+where
+    T: FromRequest<S> + Deref,
+    T::Target: Validate,
+    S: 'static,
+{
+    type Config = T::Config;
+    type Result = ValidatedFromRequest<S, T>;
+
+    #[inline]
+    fn from_request(req: &HttpRequest<S>, cfg: &Self::Config) -> Self::Result {
+        ValidatedFromRequest {
+            req: req.clone(),
+            fut: T::from_request(req, cfg),
+        }
+    }
+
+    #[inline]
+    fn is_absent(req: &HttpRequest<S>, cfg: &Self::Config) -> bool {
+        T::is_absent(req, cfg)
+    }
+}
+
+#[doc(hidden)]
+pub struct ValidatedFromRequest<S: 'static, T: FromRequest<S>> {
+    req: HttpRequest<S>,
+    fut: T::Result,
+}
+
+impl<S, T> Future for ValidatedFromRequest<S, T>
+where
+    T: FromRequest<S> + Deref,
+    T::Target: Validate,
+    S: 'static,
+{
+    type Item = Validated<T>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let item = match self.fut.poll()? {
+            Async::Ready(item) => item,
+            Async::NotReady => return Ok(Async::NotReady),
+        };
+        match item.validate() {
+            Ok(()) => Ok(Async::Ready(Validated(item))),
+            Err(errors) => Err(self.req.extractor_error(errors.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use extractor::{Query, QueryConfig};
+    use test::TestRequest;
+
+    #[derive(Deserialize, Debug)]
+    struct User {
+        name: String,
+        email: String,
+        age: u32,
+    }
+
+    impl Validate for User {
+        fn validate(&self) -> Result<(), ValidationErrors> {
+            let mut errors = ValidationErrors::new();
+            errors
+                .length("name", &self.name, 1, 8)
+                .email("email", &self.email)
+                .range("age", self.age, 18, 150);
+            errors.into_result()
+        }
+    }
+
+    #[test]
+    fn test_is_email() {
+        assert!(is_email("user@example.com"));
+        assert!(is_email("first.last+tag@sub.example.org"));
+        assert!(!is_email("user"));
+        assert!(!is_email("@example.com"));
+        assert!(!is_email("user@example"));
+        assert!(!is_email("user@exa mple.com"));
+        assert!(!is_email("user@-example.com"));
+        assert!(!is_email("user@example..com"));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let user = User {
+            name: "toolongname".to_owned(),
+            email: "user".to_owned(),
+            age: 10,
+        };
+        let errors = user.validate().err().unwrap();
+        assert_eq!(
+            errors
+                .errors()
+                .iter()
+                .map(|e| (e.field(), e.code()))
+                .collect::<Vec<_>>(),
+            vec![("name", "length"), ("email", "email"), ("age", "range")]
+        );
+        assert_eq!(
+            format!("{}", errors),
+            "Validation failed: name: length must be between 1 and 8, \
+             email: invalid email address, age: value must be between 18 and 150"
+        );
+
+        let resp = errors.error_response();
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let mut errors = ValidationErrors::new();
+        errors.nested("user", &user).required::<u32>("id", &None);
+        assert_eq!(errors.errors()[0].field(), "user.name");
+        assert_eq!(
+            errors.errors()[3],
+            FieldError::new("id", "required", "value is required")
+        );
+        assert_eq!(
+            serde_json::to_string(&errors.errors()[3]).unwrap(),
+            r#"{"field":"id","code":"required","message":"value is required"}"#
+        );
+    }
+
+    #[test]
+    fn test_validated() {
+        let req = TestRequest::with_uri("/?name=user&email=user@example.com&age=20")
+            .finish();
+        match Validated::<Query<User>>::from_request(&req, &QueryConfig::default())
+            .poll()
+            .unwrap()
+        {
+            Async::Ready(user) => assert_eq!(user.name, "user"),
+            _ => unreachable!(),
+        }
+
+        let req = TestRequest::with_uri("/?name=user&email=user&age=20").finish();
+        let err = Validated::<Query<User>>::from_request(&req, &QueryConfig::default())
+            .poll()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.cause().error_response().status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+    }
+}
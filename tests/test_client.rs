@@ -4,15 +4,22 @@ extern crate bytes;
 extern crate flate2;
 extern crate futures;
 extern crate rand;
+extern crate tokio_core;
 
+use std::collections::HashMap;
 use std::io::Read;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
 use bytes::Bytes;
 use flate2::read::GzDecoder;
 use futures::Future;
 use futures::stream::once;
 use rand::Rng;
+use tokio_core::reactor::Timeout;
 
+use actix::{Actor, Arbiter};
 use actix_web::*;
 
 const STR: &str = "Hello World Hello World Hello World Hello World Hello World \
@@ -425,3 +432,283 @@ fn test_client_cookie_handling() {
     let c2 = response.cookie("cookie2").expect("Missing cookie2");
     assert_eq!(c2, &cookie2);
 }
+
+#[test]
+fn test_client_form() {
+    let mut srv = test::TestServer::new(|app| {
+        app.resource("/", |r| {
+            r.with(|form: Form<HashMap<String, String>>| {
+                format!("{}:{}", form["name"], form["value"])
+            })
+        });
+    });
+
+    let request = srv.post()
+        .form(&[("name", "test"), ("value", "a b&c")])
+        .unwrap();
+    assert_eq!(
+        request.headers().get(http::header::CONTENT_TYPE).unwrap(),
+        "application/x-www-form-urlencoded"
+    );
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from_static(b"test:a b&c"));
+}
+
+#[test]
+fn test_client_query() {
+    let mut srv = test::TestServer::new(|app| {
+        app.resource("/", |r| {
+            r.with(|q: Query<HashMap<String, String>>| {
+                format!("{}:{}", q["page"], q["q"])
+            })
+        });
+    });
+
+    let request = srv.get()
+        .uri(srv.url("/?page=2"))
+        .query(&[("q", "actix web")])
+        .unwrap()
+        .finish()
+        .unwrap();
+    assert_eq!(request.uri().query(), Some("page=2&q=actix+web"));
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from_static(b"2:actix web"));
+}
+
+#[test]
+fn test_client_multipart() {
+    let mut srv = test::TestServer::new(|app| {
+        app.resource("/", |r| {
+            r.with(|form: MultipartForm<HashMap<String, String>>| {
+                let file = form.file("file").unwrap();
+                format!(
+                    "{}:{}:{}",
+                    form["title"],
+                    file.file_name().unwrap(),
+                    String::from_utf8_lossy(&file.read().unwrap())
+                )
+            })
+        });
+    });
+
+    // in-memory body
+    let mut form = client::MultipartBody::new();
+    form.text("title", "test")
+        .part(client::Part::bytes("file", STR).file_name("a.txt"));
+    let request = srv.post().multipart(form).unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from(format!("test:a.txt:{}", STR)));
+
+    // streaming body
+    let body = once::<_, Error>(Ok(Bytes::from_static(STR.as_ref())));
+    let mut form = client::MultipartBody::new();
+    form.text("title", "test")
+        .part(client::Part::stream("file", body).file_name("b.txt"));
+    let request = srv.post().multipart(form).unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from(format!("test:b.txt:{}", STR)));
+}
+
+#[test]
+fn test_client_cookie_jar() {
+    let mut srv = test::TestServer::new(|app| {
+        app.resource("/login", |r| {
+            r.f(|_| {
+                HttpResponse::Ok()
+                    .cookie(http::Cookie::build("session", "s1").path("/").finish())
+                    .cookie(http::Cookie::build("login", "1").path("/login").finish())
+                    .finish()
+            })
+        });
+        app.resource("/check", |r| {
+            r.f(|req| match req.cookie("session") {
+                Some(c) if c.value() == "s1" && req.cookie("login").is_none() => {
+                    HttpResponse::Ok().finish()
+                }
+                _ => HttpResponse::BadRequest().finish(),
+            })
+        });
+    });
+
+    let jar = client::CookieJar::new();
+    let request = srv.get()
+        .uri(srv.url("/login"))
+        .cookie_jar(&jar)
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(jar.len(), 2);
+
+    // jar is not used
+    let request = srv.get().uri(srv.url("/check")).finish().unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+
+    // per-request jar
+    let request = srv.get()
+        .uri(srv.url("/check"))
+        .cookie_jar(&jar)
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+
+    // connector's jar
+    let conn = client::ClientConnector::default()
+        .cookie_jar(jar.clone())
+        .start();
+    let request = srv.get()
+        .uri(srv.url("/check"))
+        .with_connector(conn)
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+}
+
+#[test]
+fn test_client_retry() {
+    let num = Arc::new(AtomicUsize::new(0));
+    let num2 = Arc::clone(&num);
+
+    let mut srv = test::TestServer::new(move |app| {
+        let num = Arc::clone(&num2);
+        app.handler(move |req: HttpRequest| {
+            if num.fetch_add(1, Ordering::Relaxed) < 2 {
+                HttpResponse::ServiceUnavailable().finish()
+            } else {
+                HttpResponse::Ok().body(format!("{}", req.method()))
+            }
+        })
+    });
+
+    let policy = client::RetryPolicy::new(3)
+        .base_delay(Duration::from_millis(10))
+        .jitter(false);
+    let request = srv.get().retry(policy.clone()).finish().unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(num.load(Ordering::Relaxed), 3);
+
+    // non-idempotent requests are not retried
+    num.store(0, Ordering::Relaxed);
+    let request = srv.client(http::Method::POST, "/")
+        .retry(policy.clone())
+        .body(STR)
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert_eq!(response.status(), http::StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(num.load(Ordering::Relaxed), 1);
+
+    // streaming body can not be replayed
+    let body = once::<_, Error>(Ok(Bytes::from_static(STR.as_ref())));
+    assert!(srv.get().retry(policy).streaming(body).is_err());
+}
+
+#[test]
+fn test_client_static_resolver() {
+    let mut srv = test::TestServer::new(|app| {
+        app.handler(|req: HttpRequest| {
+            HttpResponse::Ok().body(req.connection_info().host().to_owned())
+        })
+    });
+    let port = srv.addr().port();
+
+    let conn = client::ClientConnector::default()
+        .resolver(
+            client::StaticResolver::new()
+                .host("backend.test", "::1".parse().unwrap())
+                .host("backend.test", "127.0.0.1".parse().unwrap()),
+        )
+        .dns_cache_ttl(Duration::from_secs(60))
+        .connection_attempt_delay(Duration::from_millis(10))
+        .start();
+
+    let request = client::ClientRequest::get(format!("http://backend.test:{}/", port))
+        .with_connector(conn.clone())
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from(format!("backend.test:{}", port)));
+
+    // unknown host
+    let request = client::ClientRequest::get(format!("http://unknown.test:{}/", port))
+        .with_connector(conn)
+        .finish()
+        .unwrap();
+    assert!(srv.execute(request.send()).is_err());
+}
+
+#[test]
+fn test_client_pool_status() {
+    let mut srv = test::TestServer::new(|app| app.handler(|_| HttpResponse::Ok()));
+    let conn = client::ClientConnector::default().limit(10).start();
+
+    let snapshot = srv.execute(conn.send(client::PoolStatus)).unwrap().unwrap();
+    assert_eq!(snapshot.limit(), 10);
+    assert!(snapshot.hosts().is_empty());
+
+    let request = srv.get().with_connector(conn.clone()).finish().unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+    let _ = srv.execute(response.body()).unwrap();
+
+    let snapshot = srv.execute(conn.send(client::PoolStatus)).unwrap().unwrap();
+    let host = snapshot
+        .host(&srv.addr().ip().to_string(), srv.addr().port())
+        .unwrap();
+    assert_eq!(host.idle() + host.active(), 1);
+    assert_eq!(host.active_connections().len(), host.active());
+    assert_eq!(host.waiting(), 0);
+    assert_eq!(snapshot.wait_times().count(), 1);
+    assert_eq!(snapshot.wait_times().max(), Duration::from_secs(0));
+
+    // connection is in use
+    let request = srv.get().with_connector(conn.clone()).finish().unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    let snapshot = srv.execute(conn.send(client::PoolStatus)).unwrap().unwrap();
+    let host = snapshot
+        .host(&srv.addr().ip().to_string(), srv.addr().port())
+        .unwrap();
+    assert_eq!(host.active(), 1);
+    assert_eq!(host.active_connections().len(), 1);
+    assert_eq!(snapshot.wait_times().count(), 2);
+    let _ = srv.execute(response.body()).unwrap();
+}
+
+#[test]
+fn test_client_pool_status_prune() {
+    let mut srv = test::TestServer::new(|app| app.handler(|_| HttpResponse::Ok()));
+    let conn = client::ClientConnector::default()
+        .conn_keep_alive(Duration::from_millis(100))
+        .start();
+
+    let request = srv.get().with_connector(conn.clone()).finish().unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    let _ = srv.execute(response.body()).unwrap();
+    let snapshot = srv.execute(conn.send(client::PoolStatus)).unwrap().unwrap();
+    assert_eq!(snapshot.wait_times().count(), 1);
+
+    // idle connection is closed and endpoint state is dropped
+    // by periodic collect
+    let delay = Timeout::new(Duration::from_millis(2500), Arbiter::handle()).unwrap();
+    let _ = srv.execute(delay);
+    let snapshot = srv.execute(conn.send(client::PoolStatus)).unwrap().unwrap();
+    assert!(snapshot.hosts().is_empty());
+    assert_eq!(snapshot.wait_times().count(), 0);
+}
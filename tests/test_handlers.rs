@@ -4,15 +4,17 @@ extern crate bytes;
 extern crate futures;
 extern crate h2;
 extern crate http;
+extern crate serde_json;
 extern crate tokio_core;
 #[macro_use]
 extern crate serde_derive;
 
 use actix_web::*;
 use bytes::Bytes;
+use futures::{future, Stream};
 use http::StatusCode;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 struct PParam {
     username: String,
 }
@@ -130,6 +132,287 @@ fn test_path_and_query_extractor2() {
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 }
 
+#[test]
+fn test_tuple_extractor() {
+    let mut srv = test::TestServer::new(|app| {
+        app.resource("/{username}/index.html", |r| {
+            let mut cfg = r.route().with(
+                |(_, p, q, _, body): (
+                    HttpRequest,
+                    Path<PParam>,
+                    Query<PParam>,
+                    State<()>,
+                    String,
+                )| format!("Welcome {} - {} - {}!", p.username, q.username, body),
+            );
+            cfg.4.limit(4);
+        });
+    });
+
+    // client request
+    let request = srv.post()
+        .uri(srv.url("/test1/index.html?username=test2"))
+        .body("body")
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+
+    // read response
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from_static(b"Welcome test1 - test2 - body!"));
+
+    // payload overflow
+    let request = srv.post()
+        .uri(srv.url("/test1/index.html?username=test2"))
+        .body("large body")
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(!response.status().is_success());
+
+    // client request
+    let request = srv.get()
+        .uri(srv.url("/test1/index.html"))
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn test_async_extractor() {
+    let mut srv = test::TestServer::new(|app| {
+        app.resource("/{username}/index.html", |r| {
+            r.route()
+                .with_async(|(p, q): (Path<PParam>, Query<PParam>)| {
+                    future::ok::<_, Error>(format!(
+                        "Welcome {} - {}!",
+                        p.username, q.username
+                    ))
+                })
+        });
+    });
+
+    // client request
+    let request = srv.get()
+        .uri(srv.url("/test1/index.html?username=test2"))
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+
+    // read response
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from_static(b"Welcome test1 - test2!"));
+
+    // client request
+    let request = srv.get()
+        .uri(srv.url("/test1/index.html"))
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn test_extractor_error_handler() {
+    let mut srv = test::TestServer::with_factory(|| {
+        App::new()
+            .extractor_error_handler(|err, _| {
+                let body = format!("{{\"error\": \"{}\"}}", err);
+                error::InternalError::from_response(
+                    err,
+                    HttpResponse::BadRequest()
+                        .content_type("application/json")
+                        .body(body),
+                ).into()
+            })
+            .resource("/{id}/index.html", |r| {
+                r.route()
+                    .with(|(p, q): (Path<u32>, Query<PParam>)| {
+                        format!("Welcome {} - {}!", *p, q.username)
+                    })
+                    .0
+                    .error_handler(|err, _| {
+                        error::InternalError::from_response(
+                            err,
+                            HttpResponse::Conflict().finish(),
+                        ).into()
+                    });
+            })
+    });
+
+    let request = srv.get()
+        .uri(srv.url("/1/index.html?username=test"))
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from_static(b"Welcome 1 - test!"));
+
+    // app wide error handler
+    let request = srv.get()
+        .uri(srv.url("/1/index.html"))
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(
+        bytes,
+        Bytes::from_static(b"{\"error\": \"missing field `username`\"}")
+    );
+
+    // extractor's error handler
+    let request = srv.get()
+        .uri(srv.url("/abc/index.html?username=test"))
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+}
+
+#[derive(Deserialize, Serialize)]
+struct User {
+    name: String,
+    email: String,
+}
+
+impl Validate for User {
+    fn validate(&self) -> Result<(), error::ValidationErrors> {
+        let mut errors = error::ValidationErrors::new();
+        errors
+            .length("name", &self.name, 1, 16)
+            .email("email", &self.email);
+        errors.into_result()
+    }
+}
+
+#[test]
+fn test_validated_extractor() {
+    let mut srv = test::TestServer::new(|app| {
+        app.resource("/index.html", |r| {
+            r.method(http::Method::POST)
+                .with(|user: Validated<Json<User>>| format!("Welcome {}!", user.name))
+        });
+    });
+
+    let request = srv.post()
+        .uri(srv.url("/index.html"))
+        .json(User {
+            name: "test".to_owned(),
+            email: "test@example.com".to_owned(),
+        })
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from_static(b"Welcome test!"));
+
+    let request = srv.post()
+        .uri(srv.url("/index.html"))
+        .json(User {
+            name: "test".to_owned(),
+            email: "test".to_owned(),
+        })
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(
+        bytes,
+        Bytes::from_static(
+            b"{\"errors\":[{\"field\":\"email\",\"code\":\"email\",\
+              \"message\":\"invalid email address\"}]}"
+        )
+    );
+}
+
+#[test]
+fn test_negotiated_responder() {
+    let mut srv = test::TestServer::with_factory(|| {
+        App::new()
+            .response_format("text/csv".parse().unwrap(), |value| {
+                let value = serde_json::to_value(value)?;
+                let name = value["username"].as_str().unwrap_or("");
+                Ok(format!("username\n{}\n", name).into())
+            })
+            .resource("/index.html", |r| {
+                r.f(|_| {
+                    Negotiated(PParam {
+                        username: "test".to_owned(),
+                    })
+                })
+            })
+    });
+
+    let request = srv.get()
+        .uri(srv.url("/index.html"))
+        .header(http::header::ACCEPT, "text/csv, application/json;q=0.9")
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(response.headers()["content-type"], "text/csv");
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from_static(b"username\ntest\n"));
+
+    let request = srv.get()
+        .uri(srv.url("/index.html"))
+        .header(http::header::ACCEPT, "application/json")
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(bytes, Bytes::from_static(b"{\"username\":\"test\"}"));
+
+    let request = srv.get()
+        .uri(srv.url("/index.html"))
+        .header(http::header::ACCEPT, "image/png")
+        .finish()
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+}
+
+#[test]
+fn test_json_stream() {
+    let mut srv = test::TestServer::new(|app| {
+        app.resource("/index.html", |r| {
+            r.with(|items: JsonStream<PParam>| {
+                NdJson(items.map(|p| PParam {
+                    username: p.username.to_uppercase(),
+                }))
+            })
+        });
+    });
+
+    let request = srv.post()
+        .uri(srv.url("/index.html"))
+        .header(http::header::CONTENT_TYPE, "application/x-ndjson")
+        .body("{\"username\": \"a\"}\n{\"username\": \"b\"}\n")
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+    let bytes = srv.execute(response.body()).unwrap();
+    assert_eq!(
+        bytes,
+        Bytes::from_static(b"{\"username\":\"A\"}\n{\"username\":\"B\"}\n")
+    );
+
+    let request = srv.post()
+        .uri(srv.url("/index.html"))
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body("{\"username\": \"a\"}")
+        .unwrap();
+    let response = srv.execute(request.send()).unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
 #[test]
 fn test_non_ascii_route() {
     let mut srv = test::TestServer::new(|app| {